@@ -109,8 +109,8 @@ class PostgreSQLDriver(DatabaseDriver):
         except Exception as e:
             raise Exception(f"Erro ao listar schemas: {str(e)}")
     
-    def create_sample_data(self):
-        """Cria dados de exemplo para testes"""
+    def create_sample_data(self) -> bool:
+        """Cria dados de exemplo para testes. Retorna False se já existiam"""
         if not self.is_connected:
             raise Exception("Não conectado ao banco de dados")
         
@@ -135,7 +135,8 @@ class PostgreSQLDriver(DatabaseDriver):
                 result = conn.execute(text("SELECT COUNT(*) FROM vendas"))
                 count = result.scalar()
                 
-                if count == 0:
+                inserted = count == 0
+                if inserted:
                     # Insere dados de exemplo
                     conn.execute(text("""
                         INSERT INTO vendas (produto, quantidade, preco, data_venda, vendedor)
@@ -148,6 +149,7 @@ class PostgreSQLDriver(DatabaseDriver):
                     """))
                 
                 conn.commit()
+                return inserted
                 
         except Exception as e:
             raise Exception(f"Erro ao criar dados de exemplo: {str(e)}")
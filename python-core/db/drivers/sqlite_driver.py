@@ -72,8 +72,8 @@ class SQLiteDriver(DatabaseDriver):
         except Exception as e:
             raise Exception(f"Erro ao obter informações da tabela: {str(e)}")
     
-    def create_sample_data(self):
-        """Cria dados de exemplo para testes"""
+    def create_sample_data(self) -> bool:
+        """Cria dados de exemplo para testes. Retorna False se já existiam"""
         if not self.is_connected:
             raise Exception("Não conectado ao banco de dados")
         
@@ -93,8 +93,13 @@ class SQLiteDriver(DatabaseDriver):
                     )
                 """))
                 
-                # Insere dados de exemplo
-                conn.execute(text("""
+                # Verifica se já existem dados
+                result = conn.execute(text("SELECT COUNT(*) FROM vendas"))
+                inserted = result.scalar() == 0
+                
+                if inserted:
+                    # Insere dados de exemplo
+                    conn.execute(text("""
                     INSERT OR IGNORE INTO vendas (produto, quantidade, preco, data_venda, vendedor)
                     VALUES 
                     ('Notebook Dell', 2, 2500.00, '2024-01-15', 'João Silva'),
@@ -102,9 +107,10 @@ class SQLiteDriver(DatabaseDriver):
                     ('Teclado Mecânico', 3, 350.00, '2024-01-17', 'Pedro Costa'),
                     ('Monitor 24"', 1, 800.00, '2024-01-18', 'Ana Oliveira'),
                     ('SSD 1TB', 4, 400.00, '2024-01-19', 'Carlos Lima')
-                """))
+                    """))
                 
                 conn.commit()
+                return inserted
                 
         except Exception as e:
             raise Exception(f"Erro ao criar dados de exemplo: {str(e)}")
@@ -172,10 +172,13 @@ class DatabaseManager:
                 raise Exception("Não há conexão ativa com banco de dados")
             
             if hasattr(self.current_driver, 'create_sample_data'):
-                self.current_driver.create_sample_data()
+                inserted = self.current_driver.create_sample_data()
                 return {
                     "success": True,
-                    "message": "Dados de exemplo criados com sucesso"
+                    "created_tables": ["vendas"] if inserted else [],
+                    "skipped": not inserted,
+                    "message": "Dados de exemplo criados com sucesso" if inserted
+                    else "Os dados de exemplo já existiam neste banco"
                 }
             else:
                 raise Exception("Driver não suporta criação de dados de exemplo")
@@ -0,0 +1,37 @@
+use std::time::{Duration, Instant};
+
+// Valor único com validade; depois do ttl continua disponível como "stale" para
+// servir de fallback quando o backend estiver fora
+pub struct TimedCache<T> {
+    entry: Option<(Instant, T)>,
+    ttl: Duration,
+}
+
+impl<T: Clone> TimedCache<T> {
+    pub const fn new(ttl: Duration) -> Self {
+        TimedCache { entry: None, ttl }
+    }
+
+    pub fn get(&self) -> Option<T> {
+        match &self.entry {
+            Some((stored_at, value)) if stored_at.elapsed() < self.ttl => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn get_stale(&self) -> Option<T> {
+        self.entry.as_ref().map(|(_, value)| value.clone())
+    }
+
+    pub fn set(&mut self, value: T) {
+        self.entry = Some((Instant::now(), value));
+    }
+
+    pub fn clear(&mut self) {
+        self.entry = None;
+    }
+
+    pub fn set_ttl(&mut self, ttl: Duration) {
+        self.ttl = ttl;
+    }
+}
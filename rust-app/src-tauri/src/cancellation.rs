@@ -0,0 +1,108 @@
+use crate::error::AppError;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use tokio_util::sync::CancellationToken;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    Query,
+    Connect,
+    LogTail,
+    Watchdog,
+    SampleData,
+    KeepAlive,
+}
+
+type Registry = HashMap<Operation, Vec<(u64, CancellationToken)>>;
+
+// Tokens das operações em andamento, por tipo. Cada execução registra o seu ao
+// iniciar e o remove ao terminar, então cancelar sem nada rodando não faz nada
+static ACTIVE: LazyLock<Mutex<Registry>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+// Remove o token do registro quando a execução termina ou é descartada
+struct Registration {
+    operation: Operation,
+    id: u64,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        if let Some(tokens) = ACTIVE.lock().unwrap().get_mut(&self.operation) {
+            tokens.retain(|(token_id, _)| *token_id != self.id);
+        }
+    }
+}
+
+// O registro acontece já na chamada, não no primeiro poll, para que um cancel logo
+// após iniciar uma tarefa em segundo plano também a alcance
+pub fn run_cancellable<T, F>(operation: Operation, future: F) -> impl Future<Output = Result<T, AppError>>
+where
+    F: Future<Output = Result<T, AppError>>,
+{
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let token = CancellationToken::new();
+    ACTIVE
+        .lock()
+        .unwrap()
+        .entry(operation)
+        .or_default()
+        .push((id, token.clone()));
+    let registration = Registration { operation, id };
+
+    async move {
+        let _registration = registration;
+
+        // Ao cancelar, o future da requisição é descartado e a conexão abandonada
+        tokio::select! {
+            _ = token.cancelled() => Err(AppError::Cancelled),
+            result = future => result,
+        }
+    }
+}
+
+// Retorna quantas execuções foram canceladas
+pub fn cancel(operation: Operation) -> usize {
+    let tokens = ACTIVE
+        .lock()
+        .unwrap()
+        .remove(&operation)
+        .unwrap_or_default();
+
+    for (_, token) in &tokens {
+        token.cancel();
+    }
+
+    tokens.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_client;
+    use crate::test_support::{self, MockServer, Response};
+    use std::time::{Duration, Instant};
+
+    #[tokio::test]
+    async fn cancel_stops_a_slow_request() {
+        let _guard = test_support::serial_async().await;
+        let server = MockServer::start(|_, _| {
+            Response::json(serde_json::json!({ "success": true })).delayed(Duration::from_secs(3))
+        });
+        test_support::use_backend(&server.url());
+
+        let started = Instant::now();
+        let query = tokio::spawn(run_cancellable(Operation::Query, async {
+            api_client::send_query("total de vendas", None).await
+        }));
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        assert_eq!(cancel(Operation::Query), 1);
+        assert!(matches!(query.await.unwrap(), Err(AppError::Cancelled)));
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert_eq!(cancel(Operation::Query), 0);
+    }
+}
@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+// Tempo para o usuário ler o aviso e confirmar; depois disso precisa pedir de novo
+const TOKEN_TTL: Duration = Duration::from_secs(60);
+
+static PENDING: LazyLock<Mutex<HashMap<String, Pending>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// O token vale só para o SQL que o gerou, para não confirmar uma instrução e executar outra
+struct Pending {
+    sql: String,
+    issued_at: Instant,
+}
+
+pub fn issue(sql: &str) -> String {
+    let token = uuid::Uuid::new_v4().to_string();
+    let mut pending = PENDING.lock().unwrap();
+    pending.retain(|_, entry| entry.issued_at.elapsed() < TOKEN_TTL);
+    pending.insert(
+        token.clone(),
+        Pending {
+            sql: sql.trim().to_string(),
+            issued_at: Instant::now(),
+        },
+    );
+    token
+}
+
+// Consome o token mesmo quando a confirmação é recusada: cada token serve uma vez só
+pub fn redeem(token: &str, sql: &str) -> bool {
+    match PENDING.lock().unwrap().remove(token) {
+        Some(entry) => entry.issued_at.elapsed() < TOKEN_TTL && entry.sql == sql.trim(),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_is_single_use_and_bound_to_its_sql() {
+        let token = issue("DELETE FROM pedidos");
+        assert!(!redeem(&token, "DELETE FROM clientes"));
+        assert!(!redeem(&token, "DELETE FROM pedidos"));
+
+        let token = issue("DELETE FROM pedidos");
+        assert!(redeem(&token, "  DELETE FROM pedidos  "));
+        assert!(!redeem(&token, "DELETE FROM pedidos"));
+    }
+
+    #[test]
+    fn expired_token_is_rejected() {
+        let token = issue("DROP TABLE pedidos");
+        let expired = Instant::now().checked_sub(TOKEN_TTL + Duration::from_secs(1)).unwrap();
+        PENDING.lock().unwrap().get_mut(&token).unwrap().issued_at = expired;
+
+        assert!(!redeem(&token, "DROP TABLE pedidos"));
+    }
+}
@@ -0,0 +1,116 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+// Enviado em cada requisição para o backend saber a qual conexão ela se refere.
+// Backends antigos, de conexão única, simplesmente ignoram o cabeçalho
+pub const CONNECTION_ID_HEADER: &str = "X-Connection-Id";
+
+// Da mais antiga para a mais recente
+static CONNECTIONS: Mutex<Vec<Connection>> = Mutex::new(Vec::new());
+
+// Última configuração que conectou com sucesso, usada por reconnect. Fica apenas em
+// memória (com a senha) e nunca é gravada em disco
+static LAST_CONFIG: Mutex<Option<LastConfig>> = Mutex::new(None);
+
+#[derive(Clone)]
+pub struct LastConfig {
+    pub driver_type: String,
+    pub config: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Connection {
+    pub connection_id: String,
+    pub driver_type: String,
+    pub connected_at: String,
+}
+
+pub fn add(connection_id: &str, driver_type: &str) {
+    let mut connections = CONNECTIONS.lock().unwrap();
+    connections.retain(|connection| connection.connection_id != connection_id);
+    connections.push(Connection {
+        connection_id: connection_id.to_string(),
+        driver_type: driver_type.to_string(),
+        connected_at: chrono::Utc::now().to_rfc3339(),
+    });
+}
+
+pub fn remove(connection_id: &str) {
+    CONNECTIONS
+        .lock()
+        .unwrap()
+        .retain(|connection| connection.connection_id != connection_id);
+}
+
+// Sem id explícito, usa a conexão mais recente
+pub fn resolve(connection_id: Option<String>) -> Option<String> {
+    connection_id.or_else(|| {
+        CONNECTIONS
+            .lock()
+            .unwrap()
+            .last()
+            .map(|connection| connection.connection_id.clone())
+    })
+}
+
+pub fn active() -> Option<Connection> {
+    CONNECTIONS.lock().unwrap().last().cloned()
+}
+
+pub fn clear() {
+    CONNECTIONS.lock().unwrap().clear();
+}
+
+pub fn list() -> Vec<Connection> {
+    CONNECTIONS.lock().unwrap().clone()
+}
+
+pub fn attach(builder: reqwest::RequestBuilder, connection_id: Option<&str>) -> reqwest::RequestBuilder {
+    match connection_id {
+        Some(id) => builder.header(CONNECTION_ID_HEADER, id),
+        None => builder,
+    }
+}
+
+pub fn remember_config(driver_type: &str, config: &HashMap<String, Value>) {
+    *LAST_CONFIG.lock().unwrap() = Some(LastConfig {
+        driver_type: driver_type.to_string(),
+        config: config.clone(),
+    });
+}
+
+pub fn last_config() -> Option<LastConfig> {
+    LAST_CONFIG.lock().unwrap().clone()
+}
+
+pub fn clear_last_config() {
+    *LAST_CONFIG.lock().unwrap() = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[test]
+    fn latest_connection_is_the_default() {
+        let _guard = test_support::serial();
+        clear();
+        add("vendas", "postgresql");
+        add("estoque", "sqlite");
+
+        assert_eq!(resolve(None).as_deref(), Some("estoque"));
+        assert_eq!(resolve(Some("vendas".into())).as_deref(), Some("vendas"));
+        assert_eq!(list().len(), 2);
+
+        // Reconectar move a conexão para o fim
+        add("vendas", "postgresql");
+        assert_eq!(active().unwrap().connection_id, "vendas");
+
+        remove("vendas");
+        assert_eq!(resolve(None).as_deref(), Some("estoque"));
+        clear();
+    }
+}
@@ -0,0 +1,198 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+// Linhas listadas no relatório somando todas as categorias; além disso ele vem truncado
+const MAX_DIFF_ENTRIES: usize = 1000;
+
+#[derive(Serialize, Debug)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ChangedRow {
+    pub key: Value,
+    pub changes: Vec<FieldChange>,
+}
+
+// Linhas sem o campo chave (ou com chave null) não têm com o que ser pareadas
+#[derive(Serialize, Debug, Default)]
+pub struct Unkeyed {
+    pub before: Vec<Value>,
+    pub after: Vec<Value>,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct DiffReport {
+    pub added: Vec<Value>,
+    pub removed: Vec<Value>,
+    pub changed: Vec<ChangedRow>,
+    pub unkeyed: Unkeyed,
+    pub truncated: bool,
+}
+
+impl DiffReport {
+    fn len(&self) -> usize {
+        self.added.len()
+            + self.removed.len()
+            + self.changed.len()
+            + self.unkeyed.before.len()
+            + self.unkeyed.after.len()
+    }
+
+    // Depois do limite a entrada é descartada e o relatório marcado como truncado
+    fn has_room(&mut self) -> bool {
+        let room = self.len() < MAX_DIFF_ENTRIES;
+        if !room {
+            self.truncated = true;
+        }
+        room
+    }
+}
+
+// Valores JSON não implementam Hash; o texto serializado serve de chave. 1 e "1" são
+// chaves diferentes, como no banco
+fn row_key<'a>(row: &'a Value, key: &str) -> Option<(String, &'a Value)> {
+    match row.get(key) {
+        None | Some(Value::Null) => None,
+        Some(value) => Some((value.to_string(), value)),
+    }
+}
+
+// Campos na ordem em que aparecem em `before` e depois os que só existem em `after`;
+// campo ausente conta como null
+fn field_changes(before: &Value, after: &Value) -> Vec<FieldChange> {
+    let empty = serde_json::Map::new();
+    let before = before.as_object().unwrap_or(&empty);
+    let after = after.as_object().unwrap_or(&empty);
+
+    let fields = before.keys().chain(after.keys().filter(|field| !before.contains_key(*field)));
+    fields
+        .filter_map(|field| {
+            let old = before.get(field).cloned().unwrap_or(Value::Null);
+            let new = after.get(field).cloned().unwrap_or(Value::Null);
+            (old != new).then(|| FieldChange {
+                field: field.clone(),
+                old,
+                new,
+            })
+        })
+        .collect()
+}
+
+// Pareia as linhas pelo campo `key`. Com chaves repetidas vale a primeira ocorrência
+pub fn diff_rows(before: &[Value], after: &[Value], key: &str) -> DiffReport {
+    let mut report = DiffReport::default();
+
+    let mut after_by_key: HashMap<String, &Value> = HashMap::new();
+    for row in after {
+        match row_key(row, key) {
+            Some((text, _)) => {
+                after_by_key.entry(text).or_insert(row);
+            }
+            None if report.has_room() => report.unkeyed.after.push(row.clone()),
+            None => {}
+        }
+    }
+
+    let mut seen: HashSet<String> = HashSet::new();
+    for row in before {
+        let Some((text, key_value)) = row_key(row, key) else {
+            if report.has_room() {
+                report.unkeyed.before.push(row.clone());
+            }
+            continue;
+        };
+        if !seen.insert(text.clone()) {
+            continue;
+        }
+
+        match after_by_key.get(&text) {
+            None if report.has_room() => report.removed.push(row.clone()),
+            Some(other) => {
+                let changes = field_changes(row, other);
+                if !changes.is_empty() && report.has_room() {
+                    report.changed.push(ChangedRow {
+                        key: key_value.clone(),
+                        changes,
+                    });
+                }
+            }
+            None => {}
+        }
+    }
+
+    let mut added_keys: HashSet<String> = HashSet::new();
+    for row in after {
+        if let Some((text, _)) = row_key(row, key) {
+            if !seen.contains(&text) && added_keys.insert(text) && report.has_room() {
+                report.added.push(row.clone());
+            }
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn before() -> Vec<Value> {
+        vec![
+            json!({ "id": 1, "nome": "Ana", "total": 10 }),
+            json!({ "id": 2, "nome": "Bruno", "total": 20 }),
+        ]
+    }
+
+    #[test]
+    fn added_row() {
+        let mut after = before();
+        after.push(json!({ "id": 3, "nome": "Carla", "total": 30 }));
+
+        let report = diff_rows(&before(), &after, "id");
+        assert_eq!(report.added, [json!({ "id": 3, "nome": "Carla", "total": 30 })]);
+        assert!(report.removed.is_empty() && report.changed.is_empty());
+    }
+
+    #[test]
+    fn removed_row() {
+        let report = diff_rows(&before(), &before()[..1], "id");
+        assert_eq!(report.removed, [json!({ "id": 2, "nome": "Bruno", "total": 20 })]);
+        assert!(report.added.is_empty() && report.changed.is_empty());
+    }
+
+    #[test]
+    fn single_field_change() {
+        let mut after = before();
+        after[1]["total"] = json!(25);
+
+        let report = diff_rows(&before(), &after, "id");
+        assert_eq!(report.changed.len(), 1);
+        assert_eq!(report.changed[0].key, json!(2));
+        let change = &report.changed[0].changes;
+        assert_eq!(change.len(), 1);
+        assert_eq!((change[0].field.as_str(), &change[0].old, &change[0].new), ("total", &json!(20), &json!(25)));
+    }
+
+    #[test]
+    fn rows_without_the_key_are_kept_apart() {
+        let after = vec![json!({ "nome": "sem id" }), json!({ "id": null })];
+        let report = diff_rows(&[json!({ "nome": "antes" })], &after, "id");
+        assert_eq!(report.unkeyed.before.len(), 1);
+        assert_eq!(report.unkeyed.after.len(), 2);
+        assert!(report.added.is_empty());
+    }
+
+    #[test]
+    fn large_reports_are_truncated() {
+        let after: Vec<Value> = (0..MAX_DIFF_ENTRIES + 10).map(|id| json!({ "id": id })).collect();
+        let report = diff_rows(&[], &after, "id");
+        assert_eq!(report.added.len(), MAX_DIFF_ENTRIES);
+        assert!(report.truncated);
+    }
+}
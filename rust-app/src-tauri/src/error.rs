@@ -0,0 +1,253 @@
+use crate::i18n::{self, Msg};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::fmt;
+
+// Erro estruturado enviado ao frontend como { code, message, ... }, permitindo que a
+// interface diferencie falhas de rede (onde faz sentido tentar de novo) das demais
+#[derive(Debug, Clone)]
+pub enum AppError {
+    Network(String),
+    BackendUnavailable,
+    HostUnreachable(String),
+    Timeout,
+    Deserialize(String),
+    ResponseTooLarge(usize),
+    Security(String),
+    BackendError { status: u16, body: String },
+    QueryFailed { message: String, sql: Option<String> },
+    InvalidUrl(String),
+    InvalidDsn(String),
+    HostNotAllowed(String),
+    Cancelled,
+    Unauthorized,
+    Io(String),
+    InvalidCertificate(String),
+    AlreadyExists(String),
+    NotFound(String),
+    NoPreviousConnection,
+    NoPreviousQuery,
+    NotConnected,
+    Unsupported(String),
+    TooManyPending,
+    // Token de uso único para repetir a instrução em execute_sql_confirmed
+    ConfirmationRequired { verb: String, token: String },
+    ConfirmationInvalid,
+    ValidationError { field: String, expected: String },
+    // Erro original acrescido do id do comando que o gerou
+    Traced { request_id: String, source: Box<AppError> },
+}
+
+impl AppError {
+    pub fn with_request_id(self, request_id: String) -> Self {
+        match self {
+            AppError::Traced { .. } => self,
+            source => AppError::Traced {
+                request_id,
+                source: Box::new(source),
+            },
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Network(_) => "network",
+            AppError::BackendUnavailable => "backend_unavailable",
+            AppError::HostUnreachable(_) => "host_unreachable",
+            AppError::Timeout => "timeout",
+            AppError::Deserialize(_) => "deserialize",
+            AppError::ResponseTooLarge(_) => "response_too_large",
+            AppError::Security(_) => "security",
+            AppError::BackendError { .. } => "backend_error",
+            AppError::QueryFailed { .. } => "query_failed",
+            AppError::InvalidUrl(_) => "invalid_url",
+            AppError::InvalidDsn(_) => "invalid_dsn",
+            AppError::HostNotAllowed(_) => "host_not_allowed",
+            AppError::Cancelled => "cancelled",
+            AppError::Unauthorized => "unauthorized",
+            AppError::Io(_) => "io",
+            AppError::InvalidCertificate(_) => "invalid_certificate",
+            AppError::AlreadyExists(_) => "already_exists",
+            AppError::NotFound(_) => "not_found",
+            AppError::NoPreviousConnection => "no_previous_connection",
+            AppError::NoPreviousQuery => "no_previous_query",
+            AppError::NotConnected => "not_connected",
+            AppError::Unsupported(_) => "unsupported",
+            AppError::TooManyPending => "too_many_pending",
+            AppError::ConfirmationRequired { .. } => "confirmation_required",
+            AppError::ConfirmationInvalid => "confirmation_invalid",
+            AppError::ValidationError { .. } => "validation_error",
+            AppError::Traced { source, .. } => source.code(),
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            AppError::Network(detail) => i18n::tr(Msg::NetworkError, &[detail]),
+            AppError::BackendUnavailable => i18n::t(Msg::BackendUnavailable).to_string(),
+            AppError::HostUnreachable(detail) => i18n::tr(Msg::HostUnreachable, &[detail]),
+            AppError::Timeout => i18n::t(Msg::Timeout).to_string(),
+            AppError::Deserialize(detail) => i18n::tr(Msg::DeserializeError, &[detail]),
+            AppError::ResponseTooLarge(limit) => i18n::tr(Msg::ResponseTooLarge, &[limit]),
+            AppError::Security(message) => message.clone(),
+            AppError::BackendError { status, body } => i18n::tr(Msg::BackendError, &[status, body]),
+            AppError::QueryFailed { message, .. } => i18n::tr(Msg::QueryFailed, &[message]),
+            AppError::InvalidUrl(detail) => i18n::tr(Msg::InvalidUrl, &[detail]),
+            AppError::InvalidDsn(detail) => i18n::tr(Msg::InvalidDsn, &[detail]),
+            AppError::HostNotAllowed(host) => i18n::tr(Msg::HostNotAllowed, &[host]),
+            AppError::Cancelled => i18n::t(Msg::Cancelled).to_string(),
+            AppError::Unauthorized => i18n::t(Msg::Unauthorized).to_string(),
+            AppError::Io(detail) => i18n::tr(Msg::IoError, &[detail]),
+            AppError::InvalidCertificate(detail) => i18n::tr(Msg::InvalidCertificate, &[detail]),
+            AppError::AlreadyExists(name) => i18n::tr(Msg::AlreadyExists, &[name]),
+            AppError::NotFound(name) => i18n::tr(Msg::NotFound, &[name]),
+            AppError::NoPreviousConnection => i18n::t(Msg::NoPreviousConnection).to_string(),
+            AppError::NoPreviousQuery => i18n::t(Msg::NoPreviousQuery).to_string(),
+            AppError::NotConnected => i18n::t(Msg::NotConnected).to_string(),
+            AppError::Unsupported(feature) => i18n::tr(Msg::Unsupported, &[feature]),
+            AppError::TooManyPending => i18n::t(Msg::TooManyPending).to_string(),
+            AppError::ConfirmationRequired { verb, .. } => i18n::tr(Msg::ConfirmationRequired, &[verb]),
+            AppError::ConfirmationInvalid => i18n::t(Msg::ConfirmationInvalid).to_string(),
+            AppError::ValidationError { field, expected } => {
+                i18n::tr(Msg::FieldInvalid, &[field, expected])
+            }
+            AppError::Traced { source, .. } => source.message(),
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl std::error::Error for AppError {}
+
+// Conexão recusada significa que o host respondeu mas nada escuta na porta, ou seja,
+// o backend não está rodando. Qualquer outra falha de conexão (DNS, rota) indica
+// que o próprio host não foi alcançado
+fn is_connection_refused(err: &reqwest::Error) -> bool {
+    let mut source = std::error::Error::source(err);
+    while let Some(current) = source {
+        if let Some(io_err) = current.downcast_ref::<std::io::Error>() {
+            if io_err.kind() == std::io::ErrorKind::ConnectionRefused {
+                return true;
+            }
+        }
+        source = current.source();
+    }
+    false
+}
+
+// Erros do próprio app repassados pelo reqwest, como o redirecionamento bloqueado
+// pela lista de hosts permitidos
+fn app_error_source(err: &reqwest::Error) -> Option<AppError> {
+    let mut source = std::error::Error::source(err);
+    while let Some(current) = source {
+        if let Some(app_err) = current.downcast_ref::<AppError>() {
+            return Some(app_err.clone());
+        }
+        source = current.source();
+    }
+    None
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(err: reqwest::Error) -> Self {
+        if let Some(app_err) = app_error_source(&err) {
+            app_err
+        } else if err.is_timeout() {
+            AppError::Timeout
+        } else if err.is_connect() && is_connection_refused(&err) {
+            AppError::BackendUnavailable
+        } else if err.is_connect() {
+            AppError::HostUnreachable(err.to_string())
+        } else if err.is_decode() {
+            AppError::Deserialize(err.to_string())
+        } else {
+            AppError::Network(err.to_string())
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (error, request_id) = match self {
+            AppError::Traced { request_id, source } => (source.as_ref(), Some(request_id)),
+            error => (error, None),
+        };
+
+        let mut state = serializer.serialize_struct("AppError", 5)?;
+        state.serialize_field("code", error.code())?;
+        state.serialize_field("message", &error.message())?;
+        if let Some(request_id) = request_id {
+            state.serialize_field("request_id", request_id)?;
+        }
+        match error {
+            AppError::BackendError { status, body } => {
+                state.serialize_field("status", status)?;
+                state.serialize_field("body", body)?;
+            }
+            AppError::QueryFailed { sql: Some(sql), .. } => {
+                state.serialize_field("sql", sql)?;
+            }
+            AppError::ConfirmationRequired { verb, token } => {
+                state.serialize_field("verb", verb)?;
+                state.serialize_field("token", token)?;
+            }
+            AppError::ValidationError { field, expected } => {
+                state.serialize_field("field", field)?;
+                state.serialize_field("expected", expected)?;
+            }
+            _ => {}
+        }
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, MockServer, Response};
+
+    async fn error_for(url: &str) -> AppError {
+        let client = reqwest::Client::builder()
+            .no_proxy()
+            .timeout(std::time::Duration::from_secs(5))
+            .build()
+            .unwrap();
+        let result = match client.get(url).send().await {
+            Ok(response) => response.bytes().await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        AppError::from(result.unwrap_err())
+    }
+
+    #[tokio::test]
+    async fn connection_refused_means_backend_down() {
+        let error = error_for(&test_support::closed_port_url()).await;
+        assert!(matches!(error, AppError::BackendUnavailable), "{:?}", error);
+    }
+
+    #[tokio::test]
+    async fn unknown_host_is_unreachable() {
+        let error = error_for("http://backend.invalid:8000/health").await;
+        assert!(matches!(error, AppError::HostUnreachable(_)), "{:?}", error);
+    }
+
+    #[tokio::test]
+    async fn socket_closed_mid_response_is_a_network_error() {
+        let server = MockServer::start(|_, _| Response::json(serde_json::json!({ "ok": true })).truncated());
+        let error = error_for(&format!("{}/health", server.url())).await;
+        assert!(matches!(error, AppError::Network(_)), "{:?}", error);
+    }
+
+    #[test]
+    fn codes_keep_the_original_error() {
+        let traced = AppError::Timeout.with_request_id("abc".into());
+        assert_eq!(traced.code(), "timeout");
+        let json = serde_json::to_value(&traced).unwrap();
+        assert_eq!(json["request_id"], "abc");
+        assert_eq!(json["code"], "timeout");
+    }
+}
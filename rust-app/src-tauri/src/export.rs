@@ -0,0 +1,473 @@
+use crate::error::AppError;
+use crate::history::HistoryEntry;
+use crate::i18n::{self, Msg};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::Write;
+use std::path::Path;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    Xlsx,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Xlsx => "xlsx",
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct ExportSummary {
+    pub rows_written: usize,
+    pub warning: Option<String>,
+}
+
+// Linhas que não são objetos (ex.: um resultado escalar) viram uma única coluna
+const SCALAR_COLUMN: &str = "value";
+
+// Cabeçalhos são a união das chaves de todas as linhas, na ordem em que aparecem
+pub fn columns(rows: &[Value]) -> Vec<String> {
+    let mut columns: Vec<String> = Vec::new();
+
+    for row in rows {
+        match row {
+            Value::Object(map) => {
+                for key in map.keys() {
+                    if !columns.contains(key) {
+                        columns.push(key.clone());
+                    }
+                }
+            }
+            _ => {
+                if !columns.iter().any(|column| column == SCALAR_COLUMN) {
+                    columns.push(SCALAR_COLUMN.to_string());
+                }
+            }
+        }
+    }
+
+    columns
+}
+
+fn field<'a>(row: &'a Value, column: &str) -> Option<&'a Value> {
+    match row {
+        Value::Object(map) => map.get(column),
+        other if column == SCALAR_COLUMN => Some(other),
+        _ => None,
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnType {
+    Number,
+    Boolean,
+    String,
+    Mixed,
+}
+
+// Texto que representa um número ("12.50", vindo de colunas DECIMAL). Zeros à esquerda
+// ("007", CEPs) indicam um código, não uma quantidade, e continuam sendo texto
+fn numeric_text(text: &str) -> Option<f64> {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    let is_code = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+    if text.is_empty() || is_code || !text.chars().all(|c| c.is_ascii_digit() || "+-.eE".contains(c)) {
+        return None;
+    }
+    text.parse::<f64>().ok().filter(|number| number.is_finite())
+}
+
+fn value_type(value: &Value) -> Option<ColumnType> {
+    match value {
+        Value::Null => None,
+        Value::Number(_) => Some(ColumnType::Number),
+        Value::Bool(_) => Some(ColumnType::Boolean),
+        Value::String(text) if numeric_text(text).is_some() => Some(ColumnType::Number),
+        _ => Some(ColumnType::String),
+    }
+}
+
+// Tipo de cada coluna considerando todas as linhas. Nulos e chaves ausentes não contam;
+// uma coluna só com nulos fica como texto
+pub fn column_types(rows: &[Value], columns: &[String]) -> Vec<ColumnType> {
+    columns
+        .iter()
+        .map(|column| {
+            let mut types = rows.iter().filter_map(|row| field(row, column).and_then(value_type));
+            let first = types.next().unwrap_or(ColumnType::String);
+            if types.all(|other| other == first) {
+                first
+            } else {
+                ColumnType::Mixed
+            }
+        })
+        .collect()
+}
+
+// Chaves ausentes e nulos viram células vazias; objetos e listas são gravados como JSON
+fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => text.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn quoted(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+// Colunas numéricas saem sem aspas e todas as outras com aspas, decidindo pela coluna e não
+// pela célula: "007" ou "12" numa coluna de texto continuam texto na planilha. Nulos ficam
+// vazios, para diferenciar de texto vazio ("")
+pub fn write_csv<W: Write>(rows: &[Value], writer: W) -> Result<usize, AppError> {
+    let columns = columns(rows);
+    let types = column_types(rows, &columns);
+    // As aspas são aplicadas aqui; o writer só separa os campos
+    let mut writer = csv::WriterBuilder::new()
+        .quote_style(csv::QuoteStyle::Never)
+        .from_writer(writer);
+
+    let header: Vec<String> = columns.iter().map(|column| quoted(column)).collect();
+    writer
+        .write_record(&header)
+        .map_err(|e| AppError::Io(e.to_string()))?;
+
+    for row in rows {
+        let record: Vec<String> = columns
+            .iter()
+            .zip(&types)
+            .map(|(column, column_type)| match field(row, column) {
+                None | Some(Value::Null) => String::new(),
+                value if *column_type == ColumnType::Number => cell(value),
+                value => quoted(&cell(value)),
+            })
+            .collect();
+        writer
+            .write_record(&record)
+            .map_err(|e| AppError::Io(e.to_string()))?;
+    }
+
+    writer.flush().map_err(|e| AppError::Io(e.to_string()))?;
+    Ok(rows.len())
+}
+
+pub fn export_csv(rows: &[Value], path: &Path) -> Result<usize, AppError> {
+    let file = std::fs::File::create(path).map_err(|e| AppError::Io(e.to_string()))?;
+    write_csv(rows, file)
+}
+
+pub fn export_json(rows: &[Value], path: &Path) -> Result<usize, AppError> {
+    let text = serde_json::to_string_pretty(rows).map_err(|e| AppError::Io(e.to_string()))?;
+    std::fs::write(path, text).map_err(|e| AppError::Io(e.to_string()))?;
+    Ok(rows.len())
+}
+
+pub fn export_xlsx(rows: &[Value], path: &Path) -> Result<usize, AppError> {
+    let columns = columns(rows);
+    let mut workbook = rust_xlsxwriter::Workbook::new();
+    let sheet = workbook.add_worksheet();
+    let io_error = |e: rust_xlsxwriter::XlsxError| AppError::Io(e.to_string());
+
+    for (col, column) in columns.iter().enumerate() {
+        sheet.write_string(0, col as u16, column).map_err(io_error)?;
+    }
+
+    // Só colunas inteiramente numéricas convertem texto em número; numa coluna mista
+    // cada célula mantém o tipo original
+    let types = column_types(rows, &columns);
+
+    for (i, row) in rows.iter().enumerate() {
+        let row_index = (i + 1) as u32;
+        for (col, column) in columns.iter().enumerate() {
+            let column_type = types[col];
+            let col = col as u16;
+            match field(row, column) {
+                None | Some(Value::Null) => {}
+                Some(Value::Number(number)) => {
+                    let number = number.as_f64().unwrap_or_default();
+                    sheet.write_number(row_index, col, number).map_err(io_error)?;
+                }
+                Some(Value::String(text)) if column_type == ColumnType::Number => {
+                    let number = numeric_text(text).unwrap_or_default();
+                    sheet.write_number(row_index, col, number).map_err(io_error)?;
+                }
+                Some(Value::Bool(flag)) => {
+                    sheet.write_boolean(row_index, col, *flag).map_err(io_error)?;
+                }
+                Some(value) => {
+                    sheet.write_string(row_index, col, cell(Some(value))).map_err(io_error)?;
+                }
+            }
+        }
+    }
+
+    workbook.save(path).map_err(io_error)?;
+    Ok(rows.len())
+}
+
+// Caracteres com significado em Markdown, escapados no texto vindo do usuário ou do banco
+const MARKDOWN_SPECIAL: &str = "\\`*_{}[]<>()#+-.!|";
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            // Quebras de linha desmontariam o título ou a linha da tabela
+            '\r' | '\n' => escaped.push(' '),
+            c if MARKDOWN_SPECIAL.contains(c) => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Cerca maior que qualquer sequência de crases dentro do SQL, para ele não fechar o bloco
+fn code_fence(code: &str) -> String {
+    let mut longest = 0;
+    let mut current = 0;
+    for c in code.chars() {
+        current = if c == '`' { current + 1 } else { 0 };
+        longest = longest.max(current);
+    }
+    "`".repeat((longest + 1).max(3))
+}
+
+fn write_markdown_table<W: Write>(rows: &[Value], out: &mut W) -> std::io::Result<()> {
+    let columns = columns(rows);
+    let header: Vec<String> = columns.iter().map(|column| escape_markdown(column)).collect();
+    writeln!(out, "| {} |", header.join(" | "))?;
+    writeln!(out, "|{}", " --- |".repeat(columns.len()))?;
+
+    for row in rows {
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|column| escape_markdown(&cell(field(row, column))))
+            .collect();
+        writeln!(out, "| {} |", cells.join(" | "))?;
+    }
+    writeln!(out)
+}
+
+// Uma seção por pergunta, da mais antiga para a mais recente, com o SQL e a prévia do
+// resultado guardada no histórico
+pub fn write_session_markdown<W: Write>(entries: &[HistoryEntry], mut out: W) -> Result<usize, AppError> {
+    let io_error = |e: std::io::Error| AppError::Io(e.to_string());
+
+    writeln!(out, "# {}\n", i18n::t(Msg::SessionTitle)).map_err(io_error)?;
+
+    for entry in entries.iter().rev() {
+        let title = entry.question.as_deref().unwrap_or(i18n::t(Msg::SessionRawSql));
+        writeln!(out, "## {}\n", escape_markdown(title)).map_err(io_error)?;
+        let timestamp = i18n::tr(Msg::SessionTimestamp, &[&entry.timestamp]);
+        writeln!(out, "_{}_\n", escape_markdown(&timestamp)).map_err(io_error)?;
+
+        if let Some(sql) = entry.sql.as_deref().filter(|sql| !sql.is_empty()) {
+            let fence = code_fence(sql);
+            writeln!(out, "{fence}sql\n{}\n{fence}\n", sql.trim()).map_err(io_error)?;
+        }
+
+        if !entry.preview.is_empty() {
+            write_markdown_table(&entry.preview, &mut out).map_err(io_error)?;
+        }
+
+        if let Some(row_count) = entry.row_count {
+            writeln!(out, "{}\n", i18n::tr(Msg::SessionRowCount, &[&row_count])).map_err(io_error)?;
+        }
+    }
+
+    out.flush().map_err(io_error)?;
+    Ok(entries.len())
+}
+
+pub fn export_session(entries: &[HistoryEntry], path: &Path) -> Result<usize, AppError> {
+    let file = std::fs::File::create(path).map_err(|e| AppError::Io(e.to_string()))?;
+    write_session_markdown(entries, std::io::BufWriter::new(file))
+}
+
+// O formato escolhido sempre manda; uma extensão divergente gera apenas um aviso
+pub fn export(rows: &[Value], path: &Path, format: ExportFormat) -> Result<ExportSummary, AppError> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase());
+
+    let warning = match extension {
+        Some(ext) if ext == format.extension() => None,
+        _ => Some(i18n::tr(
+            Msg::ExportExtensionMismatch,
+            &[&path.display(), &format.extension()],
+        )),
+    };
+    if let Some(warning) = &warning {
+        log::warn!("{}", warning);
+    }
+
+    let rows_written = match format {
+        ExportFormat::Json => export_json(rows, path)?,
+        ExportFormat::Csv => export_csv(rows, path)?,
+        ExportFormat::Xlsx => export_xlsx(rows, path)?,
+    };
+
+    Ok(ExportSummary {
+        rows_written,
+        warning,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn csv_text(rows: &[Value]) -> String {
+        let mut out = Vec::new();
+        write_csv(rows, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn csv_quotes_separators_quotes_and_newlines() {
+        let rows = vec![json!({ "nome": "Silva, Ana", "obs": "disse \"oi\"\nabraços" })];
+        assert_eq!(
+            csv_text(&rows),
+            "\"nome\",\"obs\"\n\"Silva, Ana\",\"disse \"\"oi\"\"\nabraços\"\n"
+        );
+    }
+
+    #[test]
+    fn csv_ragged_rows_share_the_union_of_columns() {
+        let rows = vec![json!({ "id": 1, "nome": "Ana" }), json!({ "id": 2, "email": "b@x.com" }), json!(3)];
+        assert_eq!(
+            csv_text(&rows),
+            "\"id\",\"nome\",\"email\",\"value\"\n1,\"Ana\",,\n2,,\"b@x.com\",\n,,,3\n"
+        );
+    }
+
+    fn sample_rows() -> Vec<Value> {
+        vec![
+            json!({ "nome": "Ana", "total": 10, "ativo": true }),
+            json!({ "nome": "Bia", "total": 12.5, "ativo": false }),
+        ]
+    }
+
+    fn xlsx_part(path: &Path, name: &str) -> String {
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(path).unwrap()).unwrap();
+        let mut text = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name(name).unwrap(), &mut text).unwrap();
+        text
+    }
+
+    #[test]
+    fn json_export_round_trips() {
+        let path = crate::test_support::temp_dir().join("resultado.json");
+        let summary = export(&sample_rows(), &path, ExportFormat::Json).unwrap();
+        assert_eq!(summary.rows_written, 2);
+        assert!(summary.warning.is_none());
+
+        let read: Vec<Value> = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(read, sample_rows());
+    }
+
+    #[test]
+    fn xlsx_export_keeps_values_and_types() {
+        let path = crate::test_support::temp_dir().join("resultado.xlsx");
+        export(&sample_rows(), &path, ExportFormat::Xlsx).unwrap();
+
+        let strings = xlsx_part(&path, "xl/sharedStrings.xml");
+        for text in ["nome", "total", "ativo", "Ana", "Bia"] {
+            assert!(strings.contains(&format!(">{}<", text)), "{}", strings);
+        }
+        let sheet = xlsx_part(&path, "xl/worksheets/sheet1.xml");
+        assert!(sheet.contains("<v>10</v>") && sheet.contains("<v>12.5</v>"), "{}", sheet);
+        assert!(sheet.contains(r#"t="b""#), "{}", sheet);
+    }
+
+    #[test]
+    fn mismatched_extension_only_warns() {
+        let path = crate::test_support::temp_dir().join("resultado.txt");
+        let summary = export(&sample_rows(), &path, ExportFormat::Csv).unwrap();
+        assert!(summary.warning.is_some());
+        assert!(std::fs::read_to_string(&path).unwrap().starts_with("\"ativo\",\"nome\",\"total\"\n"));
+    }
+
+    fn type_of(values: Vec<Value>) -> ColumnType {
+        let rows: Vec<Value> = values.into_iter().map(|value| json!({ "coluna": value })).collect();
+        column_types(&rows, &["coluna".to_string()])[0]
+    }
+
+    #[test]
+    fn numeric_column_ignores_nulls() {
+        assert_eq!(type_of(vec![json!(1), json!(2.5), Value::Null, json!("12.50")]), ColumnType::Number);
+    }
+
+    #[test]
+    fn mixed_column() {
+        assert_eq!(type_of(vec![json!(1), json!("um"), json!(true)]), ColumnType::Mixed);
+        // CEP com zero à esquerda é código, não número
+        assert_eq!(type_of(vec![json!(1), json!("007")]), ColumnType::Mixed);
+    }
+
+    #[test]
+    fn boolean_column() {
+        assert_eq!(type_of(vec![json!(true), Value::Null, json!(false)]), ColumnType::Boolean);
+        assert_eq!(type_of(vec![Value::Null]), ColumnType::String);
+    }
+
+    #[test]
+    fn csv_leaves_numbers_unquoted() {
+        let rows = vec![json!({ "id": 1, "cep": "01310" }), json!({ "id": 2, "cep": "20040" })];
+        assert_eq!(csv_text(&rows), "\"cep\",\"id\"\n\"01310\",1\n\"20040\",2\n");
+    }
+
+    fn session_entry(question: &str, sql: Option<&str>, preview: Vec<Value>) -> HistoryEntry {
+        HistoryEntry {
+            question: Some(question.to_string()),
+            sql: sql.map(str::to_string),
+            timestamp: "2026-10-14T10:00:00Z".to_string(),
+            row_count: Some(preview.len()),
+            preview,
+        }
+    }
+
+    #[test]
+    fn session_markdown_has_a_section_per_entry() {
+        let _guard = crate::test_support::serial();
+        i18n::set_lang(i18n::Lang::PtBr);
+        // O histórico guarda a pergunta mais recente primeiro
+        let entries = vec![
+            session_entry("clientes *ativos*?", None, Vec::new()),
+            session_entry("total por mês", Some("SELECT mes, sum(total) FROM vendas GROUP BY mes"), vec![
+                json!({ "mes": "01", "total": 10 }),
+                json!({ "mes": "02", "total": 12 }),
+            ]),
+        ];
+
+        let mut out = Vec::new();
+        assert_eq!(write_session_markdown(&entries, &mut out).unwrap(), 2);
+        let text = String::from_utf8(out).unwrap();
+
+        let headings: Vec<&str> = text.lines().filter(|line| line.starts_with('#')).collect();
+        assert_eq!(headings, [
+            format!("# {}", i18n::t(Msg::SessionTitle)).as_str(),
+            "## total por mês",
+            "## clientes \\*ativos\\*?",
+        ]);
+        assert!(text.contains("```sql\nSELECT mes, sum(total) FROM vendas GROUP BY mes\n```\n"), "{}", text);
+        assert!(text.contains("| mes | total |\n| --- | --- |\n| 01 | 10 |\n| 02 | 12 |\n"), "{}", text);
+        assert!(text.contains("2026\\-10\\-14T10:00:00Z"), "{}", text);
+        // A segunda pergunta não tem SQL nem linhas
+        assert_eq!(text.matches("```sql").count(), 1);
+    }
+}
@@ -0,0 +1,86 @@
+use crate::error::AppError;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+const FAVORITES_FILE: &str = "favorites.json";
+
+// Na ordem em que foram salvos
+static FAVORITES: Mutex<Vec<Favorite>> = Mutex::new(Vec::new());
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Favorite {
+    pub name: String,
+    pub question: String,
+    pub created_at: String,
+}
+
+pub fn load() {
+    *FAVORITES.lock().unwrap() = storage::load(FAVORITES_FILE);
+}
+
+pub fn save(name: &str, question: &str) -> Result<Favorite, AppError> {
+    let name = name.trim();
+    let mut favorites = FAVORITES.lock().unwrap();
+
+    if favorites.iter().any(|favorite| favorite.name == name) {
+        return Err(AppError::AlreadyExists(name.to_string()));
+    }
+
+    let favorite = Favorite {
+        name: name.to_string(),
+        question: question.to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+    favorites.push(favorite.clone());
+    storage::save(FAVORITES_FILE, &*favorites)?;
+
+    Ok(favorite)
+}
+
+pub fn list() -> Vec<Favorite> {
+    FAVORITES.lock().unwrap().clone()
+}
+
+pub fn delete(name: &str) -> Result<(), AppError> {
+    let mut favorites = FAVORITES.lock().unwrap();
+
+    let before = favorites.len();
+    favorites.retain(|favorite| favorite.name != name);
+    if favorites.len() == before {
+        return Err(AppError::NotFound(name.to_string()));
+    }
+
+    storage::save(FAVORITES_FILE, &*favorites)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn names() -> Vec<String> {
+        list().into_iter().map(|favorite| favorite.name).collect()
+    }
+
+    #[test]
+    fn save_list_duplicate_and_delete() {
+        let _guard = test_support::serial();
+        storage::set_data_dir(test_support::temp_dir());
+        load();
+
+        save("vendas do mês", "total de vendas deste mês").unwrap();
+        save(" clientes ", "clientes ativos").unwrap();
+        assert_eq!(names(), ["vendas do mês", "clientes"]);
+
+        assert!(matches!(save("clientes", "outra pergunta"), Err(AppError::AlreadyExists(_))));
+
+        delete("vendas do mês").unwrap();
+        assert_eq!(names(), ["clientes"]);
+        assert!(matches!(delete("vendas do mês"), Err(AppError::NotFound(_))));
+
+        // Persistido no disco
+        load();
+        assert_eq!(names(), ["clientes"]);
+    }
+}
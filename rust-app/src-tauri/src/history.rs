@@ -0,0 +1,136 @@
+use crate::error::AppError;
+use crate::redaction;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Mutex;
+
+const HISTORY_FILE: &str = "query_history.json";
+const MAX_ENTRIES: usize = 500;
+
+// Primeiras linhas do resultado, para a transcrição da sessão; o resultado inteiro
+// faria o arquivo de histórico crescer sem limite
+const PREVIEW_ROWS: usize = 5;
+
+// Mais recentes primeiro
+static HISTORY: Mutex<Vec<HistoryEntry>> = Mutex::new(Vec::new());
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HistoryEntry {
+    // None quando o SQL foi executado direto, sem pergunta
+    pub question: Option<String>,
+    pub sql: Option<String>,
+    pub timestamp: String,
+    pub row_count: Option<usize>,
+    // Entradas gravadas por versões anteriores não têm prévia
+    #[serde(default)]
+    pub preview: Vec<Value>,
+}
+
+pub fn load() {
+    let mut entries: Vec<HistoryEntry> = storage::load(HISTORY_FILE);
+    entries.truncate(MAX_ENTRIES);
+    *HISTORY.lock().unwrap() = entries;
+}
+
+pub fn record(
+    question: &str,
+    sql: Option<String>,
+    row_count: Option<usize>,
+    rows: &[Value],
+) -> Result<(), AppError> {
+    insert(Some(question.to_string()), sql, row_count, rows)
+}
+
+pub fn record_sql(sql: &str, row_count: Option<usize>, rows: &[Value]) -> Result<(), AppError> {
+    insert(None, Some(sql.to_string()), row_count, rows)
+}
+
+fn insert(
+    question: Option<String>,
+    sql: Option<String>,
+    row_count: Option<usize>,
+    rows: &[Value],
+) -> Result<(), AppError> {
+    let preview = rows.iter().take(PREVIEW_ROWS).map(redaction::redact_value).collect();
+    let mut history = HISTORY.lock().unwrap();
+
+    history.insert(
+        0,
+        HistoryEntry {
+            question,
+            sql,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            row_count,
+            preview,
+        },
+    );
+    history.truncate(MAX_ENTRIES);
+
+    storage::save(HISTORY_FILE, &*history)
+}
+
+pub fn entries(limit: usize) -> Vec<HistoryEntry> {
+    HISTORY.lock().unwrap().iter().take(limit).cloned().collect()
+}
+
+pub fn clear() -> Result<(), AppError> {
+    let mut history = HISTORY.lock().unwrap();
+    history.clear();
+    storage::save(HISTORY_FILE, &*history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, SerialGuard};
+
+    fn empty_history() -> SerialGuard {
+        let guard = test_support::serial();
+        storage::set_data_dir(test_support::temp_dir());
+        clear().unwrap();
+        guard
+    }
+
+    fn questions() -> Vec<String> {
+        entries(usize::MAX).into_iter().filter_map(|entry| entry.question).collect()
+    }
+
+    #[test]
+    fn most_recent_first() {
+        let _guard = empty_history();
+        for question in ["primeira", "segunda", "terceira"] {
+            record(question, None, None, &[]).unwrap();
+        }
+        assert_eq!(questions(), ["terceira", "segunda", "primeira"]);
+
+        // O arquivo guarda a mesma ordem
+        load();
+        assert_eq!(questions(), ["terceira", "segunda", "primeira"]);
+    }
+
+    #[test]
+    fn oldest_entry_is_evicted_past_the_cap() {
+        let _guard = empty_history();
+        for i in 0..=MAX_ENTRIES {
+            record(&format!("pergunta {}", i), None, None, &[]).unwrap();
+        }
+
+        let questions = questions();
+        assert_eq!(questions.len(), MAX_ENTRIES);
+        assert_eq!(questions[0], format!("pergunta {}", MAX_ENTRIES));
+        assert_eq!(questions.last().unwrap(), "pergunta 1");
+    }
+
+    #[test]
+    fn raw_sql_is_not_recorded_as_a_question() {
+        let _guard = empty_history();
+        record("quantos clientes?", Some("SELECT count(*) FROM clientes".into()), Some(1), &[]).unwrap();
+        record_sql("DELETE FROM rascunhos", Some(3), &[]).unwrap();
+
+        let entries = entries(usize::MAX);
+        assert_eq!(entries[0].question, None);
+        assert_eq!(entries[0].sql.as_deref(), Some("DELETE FROM rascunhos"));
+        assert_eq!(questions(), ["quantos clientes?"]);
+    }
+}
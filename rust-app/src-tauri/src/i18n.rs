@@ -0,0 +1,419 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::sync::Mutex;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lang {
+    #[default]
+    #[serde(rename = "pt-BR")]
+    PtBr,
+    #[serde(rename = "en")]
+    En,
+}
+
+static LANG: Mutex<Lang> = Mutex::new(Lang::PtBr);
+
+// Identificadores das mensagens exibidas ao usuário. Cada "{}" no texto do catálogo
+// é preenchido, em ordem, pelos argumentos passados a `tr`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    // Formatação do resultado de send_query
+    AiLabel,
+    SqlLabel,
+    ResultLabel,
+    NoResults,
+    RecordsFound,
+    MoreRecords,
+    TotalRecords,
+    TablesAvailable,
+    UnknownError,
+    ReadResponseFailed,
+
+    // Segurança
+    BlockedKeyword,
+    BlockedComment,
+    BlockedUnionSelect,
+    BlockedStackedStatements,
+    TooLong,
+    InvalidTableName,
+
+    // Erros
+    NetworkError,
+    BackendUnavailable,
+    HostUnreachable,
+    Timeout,
+    DeserializeError,
+    ResponseTooLarge,
+    BackendError,
+    QueryFailed,
+    InvalidUrl,
+    InvalidDsn,
+    DsnMissingDatabase,
+    HostNotAllowed,
+    UnsupportedScheme,
+    MissingHost,
+    CredentialsInUrl,
+    Cancelled,
+    Unauthorized,
+    IoError,
+    InvalidCertificate,
+    AlreadyExists,
+    NotFound,
+    NoPreviousConnection,
+    NoPreviousQuery,
+    NotConnected,
+    RecentErrorsCleared,
+    ConfirmationRequired,
+    ConfirmationInvalid,
+    Unsupported,
+    TooManyPending,
+    FieldInvalid,
+    ExpectedRequired,
+    ExpectedOneOf,
+
+    // Respostas dos comandos
+    QueryCancelled,
+    NoQueryRunning,
+    ConnectCancelled,
+    NoConnectRunning,
+    SampleDataCancelled,
+    NoSampleDataRunning,
+    SampleDataSkipped,
+    LogTailStopped,
+    NoLogTailRunning,
+    WatchdogStarted,
+    WatchdogStopped,
+    NoWatchdogRunning,
+    KeepAliveStarted,
+    KeepAliveStopped,
+    NoKeepAliveRunning,
+    HistoryCleared,
+    FavoriteRemoved,
+    SecurityPolicyUpdated,
+    SecurityModeUpdated,
+    ReadOnlyEnabled,
+    ReadOnlyDisabled,
+    SecurityModeBlacklist,
+    SecurityModeStrictReadOnly,
+    MaxLengthUpdated,
+    BackendUrlUpdated,
+    TimeoutsUpdated,
+    RetryPolicyUpdated,
+    MaxResponseSizeUpdated,
+    LogLevelUpdated,
+    AuthTokenUpdated,
+    AuthTokenRemoved,
+    CaCertificateUpdated,
+    CaCertificateRemoved,
+    ProxyUpdated,
+    ProxyRemoved,
+    DriverCacheTtlUpdated,
+    PreviewLimitUpdated,
+    RowThresholdUpdated,
+    OutputFormatEmoji,
+    OutputFormatPlain,
+    MaxConcurrentUpdated,
+    LanguageUpdated,
+    DefaultDriverUpdated,
+    ConnectionStateReset,
+    ShutdownDisconnected,
+    OfflineQueueEnabled,
+    OfflineQueueDisabled,
+    PendingQueueCleared,
+    ExportExtensionMismatch,
+    SessionTitle,
+    SessionRawSql,
+    SessionTimestamp,
+    SessionRowCount,
+}
+
+fn pt_br(msg: Msg) -> &'static str {
+    match msg {
+        Msg::AiLabel => "IA:",
+        Msg::SqlLabel => "SQL:",
+        Msg::ResultLabel => "Resultado:",
+        Msg::NoResults => "Nenhum resultado encontrado.",
+        Msg::RecordsFound => "{} registros encontrados:",
+        Msg::MoreRecords => "... e mais {} registros",
+        Msg::TotalRecords => "Total de registros: {}",
+        Msg::TablesAvailable => "Tabelas disponíveis: {}",
+        Msg::UnknownError => "Erro desconhecido",
+        Msg::ReadResponseFailed => "Erro ao ler resposta",
+
+        Msg::BlockedKeyword => "Entrada bloqueada por segurança: a palavra \"{}\" não é permitida.",
+        Msg::BlockedComment => "Entrada bloqueada por segurança: comentários SQL não são permitidos.",
+        Msg::BlockedUnionSelect => "Entrada bloqueada por segurança: UNION SELECT não é permitido.",
+        Msg::BlockedStackedStatements => {
+            "Entrada bloqueada por segurança: múltiplas instruções separadas por ';' não são permitidas."
+        }
+        Msg::TooLong => "Pergunta muito longa: o limite é de {} caracteres.",
+        Msg::InvalidTableName => "Entrada bloqueada por segurança: nome de tabela inválido \"{}\"",
+
+        Msg::NetworkError => "Erro ao conectar com backend: {}",
+        Msg::BackendUnavailable => {
+            "O backend não está rodando: inicie o servidor Python e tente novamente"
+        }
+        Msg::HostUnreachable => {
+            "Não foi possível alcançar o servidor do backend; verifique a URL e a rede: {}"
+        }
+        Msg::Timeout => "O servidor demorou demais para responder",
+        Msg::DeserializeError => "Erro ao processar resposta: {}",
+        Msg::ResponseTooLarge => "A resposta do backend passou do limite de {} bytes",
+        Msg::BackendError => "O backend retornou erro {}: {}",
+        Msg::QueryFailed => "❌ {}",
+        Msg::InvalidUrl => "URL do backend inválida: {}",
+        Msg::InvalidDsn => "Connection string inválida: {}",
+        Msg::DsnMissingDatabase => "falta o nome ou caminho do banco",
+        Msg::HostNotAllowed => "O host \"{}\" não está na lista de hosts permitidos",
+        Msg::UnsupportedScheme => "esquema \"{}\" não suportado",
+        Msg::MissingHost => "a URL precisa ter um host",
+        Msg::CredentialsInUrl => "a URL não pode conter usuário ou senha",
+        Msg::Cancelled => "Operação cancelada pelo usuário",
+        Msg::Unauthorized => "Não autorizado: faça login novamente para continuar",
+        Msg::IoError => "Erro ao gravar arquivo: {}",
+        Msg::InvalidCertificate => "Certificado inválido: {}",
+        Msg::AlreadyExists => "Já existe um item com o nome \"{}\"",
+        Msg::NotFound => "Não encontrado: {}",
+        Msg::NoPreviousConnection => {
+            "Nenhuma conexão anterior para reconectar. Conecte-se a um banco primeiro."
+        }
+        Msg::NoPreviousQuery => "Nenhuma consulta anterior para repetir. Faça uma pergunta primeiro.",
+        Msg::NotConnected => "Nenhum banco conectado. Conecte-se a um banco primeiro.",
+        Msg::RecentErrorsCleared => "Lista de erros recentes limpa",
+        Msg::ConfirmationRequired => "A instrução usa {} e altera o banco. Confirme para executar.",
+        Msg::ConfirmationInvalid => "Confirmação expirada ou inválida. Execute a instrução de novo.",
+        Msg::Unsupported => "O backend atual não oferece o recurso \"{}\"",
+        Msg::TooManyPending => {
+            "Muitas consultas na fila: aguarde as anteriores terminarem ou cancele-as"
+        }
+        Msg::FieldInvalid => "Campo \"{}\" inválido: esperado {}",
+        Msg::ExpectedRequired => "um valor obrigatório ({})",
+        Msg::ExpectedOneOf => "um destes drivers: {}",
+
+        Msg::QueryCancelled => "Consulta cancelada",
+        Msg::NoQueryRunning => "Nenhuma consulta em andamento",
+        Msg::ConnectCancelled => "Conexão cancelada",
+        Msg::NoConnectRunning => "Nenhuma conexão em andamento",
+        Msg::SampleDataCancelled => "Criação dos dados de exemplo cancelada; parte dos dados pode ter sido criada",
+        Msg::NoSampleDataRunning => "Nenhuma criação de dados de exemplo em andamento",
+        Msg::SampleDataSkipped => "Os dados de exemplo já foram criados nesta conexão",
+        Msg::LogTailStopped => "Acompanhamento dos logs encerrado",
+        Msg::NoLogTailRunning => "Os logs não estavam sendo acompanhados",
+        Msg::WatchdogStarted => "Verificando a conexão a cada {} ms",
+        Msg::WatchdogStopped => "Verificação da conexão encerrada",
+        Msg::NoWatchdogRunning => "A conexão não estava sendo verificada",
+        Msg::KeepAliveStarted => "Mantendo a sessão ativa com um ping a cada {} s",
+        Msg::KeepAliveStopped => "Pings de manutenção da sessão encerrados",
+        Msg::NoKeepAliveRunning => "Nenhum ping de manutenção em andamento",
+        Msg::HistoryCleared => "Histórico de consultas apagado",
+        Msg::FavoriteRemoved => "Favorito \"{}\" removido",
+        Msg::SecurityPolicyUpdated => "Política de segurança atualizada: {}",
+        Msg::SecurityModeUpdated => "Modo de segurança atualizado: {}",
+        Msg::ReadOnlyEnabled => "Somente leitura ativado até desconectar",
+        Msg::ReadOnlyDisabled => "Somente leitura desativado; vale o modo de segurança configurado",
+        Msg::SecurityModeBlacklist => "lista de bloqueio",
+        Msg::SecurityModeStrictReadOnly => "somente leitura",
+        Msg::MaxLengthUpdated => "Limite de tamanho da pergunta atualizado: {} caracteres",
+        Msg::BackendUrlUpdated => "Backend configurado para {}",
+        Msg::TimeoutsUpdated => "Timeouts atualizados: conexão {}s, requisição {}s",
+        Msg::RetryPolicyUpdated => {
+            "Política de novas tentativas atualizada: {} tentativas, espera inicial de {}ms"
+        }
+        Msg::MaxResponseSizeUpdated => "Respostas limitadas a {} bytes",
+        Msg::LogLevelUpdated => "Nível de log: {}",
+        Msg::AuthTokenUpdated => "Token de autenticação atualizado",
+        Msg::AuthTokenRemoved => "Token de autenticação removido",
+        Msg::CaCertificateUpdated => "Certificado da CA carregado de {}",
+        Msg::CaCertificateRemoved => "Usando apenas os certificados do sistema",
+        Msg::ProxyUpdated => "Requisições ao backend passam pelo proxy {}",
+        Msg::ProxyRemoved => "Proxy removido; valem as variáveis HTTP_PROXY/HTTPS_PROXY, se definidas",
+        Msg::DriverCacheTtlUpdated => "Cache de drivers válido por {}s",
+        Msg::PreviewLimitUpdated => "Prévia do resultado limitada a {} registros",
+        Msg::RowThresholdUpdated => "Resultados com mais de {} registros serão cortados",
+        Msg::OutputFormatEmoji => "Respostas formatadas com emoji",
+        Msg::OutputFormatPlain => "Respostas formatadas em texto simples",
+        Msg::MaxConcurrentUpdated => "Até {} consultas simultâneas",
+        Msg::LanguageUpdated => "Idioma atualizado para português",
+        Msg::DefaultDriverUpdated => "Driver padrão: {}",
+        Msg::ConnectionStateReset => "Estado local de conexão apagado",
+        Msg::ShutdownDisconnected => "{} conexões encerradas",
+        Msg::OfflineQueueEnabled => "Perguntas feitas sem conexão serão refeitas quando a rede voltar",
+        Msg::OfflineQueueDisabled => "Fila offline desativada",
+        Msg::PendingQueueCleared => "{} perguntas removidas da fila offline",
+        Msg::ExportExtensionMismatch => {
+            "O arquivo {} não tem a extensão .{} esperada para o formato escolhido"
+        }
+        Msg::SessionTitle => "Sessão de consultas",
+        Msg::SessionRawSql => "SQL executado",
+        Msg::SessionTimestamp => "Executada em {}",
+        Msg::SessionRowCount => "Total de registros: {}",
+    }
+}
+
+fn en(msg: Msg) -> &'static str {
+    match msg {
+        Msg::AiLabel => "AI:",
+        Msg::SqlLabel => "SQL:",
+        Msg::ResultLabel => "Result:",
+        Msg::NoResults => "No results found.",
+        Msg::RecordsFound => "{} records found:",
+        Msg::MoreRecords => "... and {} more records",
+        Msg::TotalRecords => "Total records: {}",
+        Msg::TablesAvailable => "Available tables: {}",
+        Msg::UnknownError => "Unknown error",
+        Msg::ReadResponseFailed => "Failed to read response",
+
+        Msg::BlockedKeyword => "Input blocked for security: the word \"{}\" is not allowed.",
+        Msg::BlockedComment => "Input blocked for security: SQL comments are not allowed.",
+        Msg::BlockedUnionSelect => "Input blocked for security: UNION SELECT is not allowed.",
+        Msg::BlockedStackedStatements => {
+            "Input blocked for security: multiple statements separated by ';' are not allowed."
+        }
+        Msg::TooLong => "Question too long: the limit is {} characters.",
+        Msg::InvalidTableName => "Input blocked for security: invalid table name \"{}\"",
+
+        Msg::NetworkError => "Failed to connect to backend: {}",
+        Msg::BackendUnavailable => "The backend is not running: start the Python server and try again",
+        Msg::HostUnreachable => "Could not reach the backend host; check the URL and network: {}",
+        Msg::Timeout => "The server took too long to respond",
+        Msg::DeserializeError => "Failed to process response: {}",
+        Msg::ResponseTooLarge => "The backend response exceeded the {} byte limit",
+        Msg::BackendError => "The backend returned error {}: {}",
+        Msg::QueryFailed => "❌ {}",
+        Msg::InvalidUrl => "Invalid backend URL: {}",
+        Msg::InvalidDsn => "Invalid connection string: {}",
+        Msg::DsnMissingDatabase => "the database name or path is missing",
+        Msg::HostNotAllowed => "Host \"{}\" is not on the allowed hosts list",
+        Msg::UnsupportedScheme => "unsupported scheme \"{}\"",
+        Msg::MissingHost => "the URL must have a host",
+        Msg::CredentialsInUrl => "the URL must not contain a username or password",
+        Msg::Cancelled => "Operation cancelled by the user",
+        Msg::Unauthorized => "Unauthorized: please log in again to continue",
+        Msg::IoError => "Failed to write file: {}",
+        Msg::InvalidCertificate => "Invalid certificate: {}",
+        Msg::AlreadyExists => "An item named \"{}\" already exists",
+        Msg::NotFound => "Not found: {}",
+        Msg::NoPreviousConnection => "No previous connection to reconnect. Connect to a database first.",
+        Msg::NoPreviousQuery => "No previous query to run again. Ask a question first.",
+        Msg::NotConnected => "No database connected. Connect to a database first.",
+        Msg::RecentErrorsCleared => "Recent errors cleared",
+        Msg::ConfirmationRequired => "The statement uses {} and changes the database. Confirm to run it.",
+        Msg::ConfirmationInvalid => "Confirmation expired or invalid. Run the statement again.",
+        Msg::Unsupported => "The current backend does not offer the \"{}\" feature",
+        Msg::TooManyPending => "Too many queries pending: wait for the previous ones or cancel them",
+        Msg::FieldInvalid => "Invalid field \"{}\": expected {}",
+        Msg::ExpectedRequired => "a required value ({})",
+        Msg::ExpectedOneOf => "one of these drivers: {}",
+
+        Msg::QueryCancelled => "Query cancelled",
+        Msg::NoQueryRunning => "No query running",
+        Msg::ConnectCancelled => "Connection cancelled",
+        Msg::NoConnectRunning => "No connection in progress",
+        Msg::SampleDataCancelled => "Sample data creation cancelled; some data may already have been created",
+        Msg::NoSampleDataRunning => "No sample data creation in progress",
+        Msg::SampleDataSkipped => "Sample data was already created for this connection",
+        Msg::LogTailStopped => "Stopped following backend logs",
+        Msg::NoLogTailRunning => "Backend logs were not being followed",
+        Msg::WatchdogStarted => "Checking the connection every {} ms",
+        Msg::WatchdogStopped => "Stopped checking the connection",
+        Msg::NoWatchdogRunning => "The connection was not being checked",
+        Msg::KeepAliveStarted => "Keeping the session alive with a ping every {} s",
+        Msg::KeepAliveStopped => "Session keep-alive pings stopped",
+        Msg::NoKeepAliveRunning => "No keep-alive pings running",
+        Msg::HistoryCleared => "Query history cleared",
+        Msg::FavoriteRemoved => "Favorite \"{}\" removed",
+        Msg::SecurityPolicyUpdated => "Security policy updated: {}",
+        Msg::SecurityModeUpdated => "Security mode updated: {}",
+        Msg::ReadOnlyEnabled => "Read-only enabled until you disconnect",
+        Msg::ReadOnlyDisabled => "Read-only disabled; the configured security mode applies",
+        Msg::SecurityModeBlacklist => "blacklist",
+        Msg::SecurityModeStrictReadOnly => "strict read-only",
+        Msg::MaxLengthUpdated => "Question length limit updated: {} characters",
+        Msg::BackendUrlUpdated => "Backend set to {}",
+        Msg::TimeoutsUpdated => "Timeouts updated: connect {}s, request {}s",
+        Msg::RetryPolicyUpdated => "Retry policy updated: {} retries, initial delay of {}ms",
+        Msg::MaxResponseSizeUpdated => "Responses limited to {} bytes",
+        Msg::LogLevelUpdated => "Log level: {}",
+        Msg::AuthTokenUpdated => "Authentication token updated",
+        Msg::AuthTokenRemoved => "Authentication token removed",
+        Msg::CaCertificateUpdated => "CA certificate loaded from {}",
+        Msg::CaCertificateRemoved => "Using only the system certificates",
+        Msg::ProxyUpdated => "Backend requests go through the proxy {}",
+        Msg::ProxyRemoved => "Proxy removed; HTTP_PROXY/HTTPS_PROXY apply if set",
+        Msg::DriverCacheTtlUpdated => "Driver cache valid for {}s",
+        Msg::PreviewLimitUpdated => "Result preview limited to {} records",
+        Msg::RowThresholdUpdated => "Results with more than {} records will be truncated",
+        Msg::OutputFormatEmoji => "Responses formatted with emoji",
+        Msg::OutputFormatPlain => "Responses formatted as plain text",
+        Msg::MaxConcurrentUpdated => "Up to {} concurrent queries",
+        Msg::LanguageUpdated => "Language set to English",
+        Msg::DefaultDriverUpdated => "Default driver: {}",
+        Msg::ConnectionStateReset => "Local connection state cleared",
+        Msg::ShutdownDisconnected => "{} connections closed",
+        Msg::OfflineQueueEnabled => "Questions asked while offline will be retried when the network is back",
+        Msg::OfflineQueueDisabled => "Offline queue disabled",
+        Msg::PendingQueueCleared => "{} questions removed from the offline queue",
+        Msg::ExportExtensionMismatch => {
+            "The file {} does not have the .{} extension expected for the chosen format"
+        }
+        Msg::SessionTitle => "Query session",
+        Msg::SessionRawSql => "Executed SQL",
+        Msg::SessionTimestamp => "Run at {}",
+        Msg::SessionRowCount => "Total rows: {}",
+    }
+}
+
+pub fn set_lang(lang: Lang) {
+    *LANG.lock().unwrap() = lang;
+}
+
+pub fn lang() -> Lang {
+    *LANG.lock().unwrap()
+}
+
+pub fn t(msg: Msg) -> &'static str {
+    match lang() {
+        Lang::PtBr => pt_br(msg),
+        Lang::En => en(msg),
+    }
+}
+
+pub fn tr(msg: Msg, args: &[&dyn Display]) -> String {
+    let mut parts = t(msg).split("{}");
+    let mut text = parts.next().unwrap_or_default().to_string();
+
+    for (i, part) in parts.enumerate() {
+        if let Some(arg) = args.get(i) {
+            text.push_str(&arg.to_string());
+        }
+        text.push_str(part);
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[test]
+    fn switching_language_changes_the_message() {
+        let _guard = test_support::serial();
+        set_lang(Lang::PtBr);
+        let portuguese = tr(Msg::FavoriteRemoved, &[&"vendas"]);
+
+        set_lang(Lang::En);
+        let english = tr(Msg::FavoriteRemoved, &[&"vendas"]);
+        set_lang(Lang::PtBr);
+
+        assert_ne!(portuguese, english);
+        assert!(portuguese.contains("vendas") && english.contains("vendas"));
+    }
+
+    #[test]
+    fn language_codes_match_the_frontend() {
+        assert_eq!(serde_json::from_str::<Lang>("\"pt-BR\"").unwrap(), Lang::PtBr);
+        assert_eq!(serde_json::from_str::<Lang>("\"en\"").unwrap(), Lang::En);
+    }
+}
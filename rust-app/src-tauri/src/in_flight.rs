@@ -0,0 +1,73 @@
+use crate::api_client::QueryResponse;
+use crate::error::AppError;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{LazyLock, Mutex};
+use tokio::sync::watch;
+
+type Outcome = Option<Result<QueryResponse, AppError>>;
+
+// Perguntas em andamento por chave; um clique duplo encontra a primeira ainda aqui e
+// espera pelo mesmo resultado em vez de gerar outra chamada à IA
+static QUERIES: LazyLock<Mutex<HashMap<String, watch::Receiver<Outcome>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// Espaços nas pontas e maiúsculas não mudam a pergunta; a conexão muda
+pub fn query_key(question: &str, connection_id: Option<&str>) -> String {
+    format!("{}\n{}", connection_id.unwrap_or_default(), question.trim().to_lowercase())
+}
+
+// Remove a entrada mesmo quando a primeira chamada é cancelada no meio do caminho
+struct Entry {
+    key: String,
+}
+
+impl Drop for Entry {
+    fn drop(&mut self) {
+        QUERIES.lock().unwrap().remove(&self.key);
+    }
+}
+
+// A primeira chamada com a chave executa `request`; as que chegam enquanto ela roda
+// recebem uma cópia do mesmo resultado
+pub async fn share_query<F>(key: String, request: F) -> Result<QueryResponse, AppError>
+where
+    F: Future<Output = Result<QueryResponse, AppError>>,
+{
+    let (sender, receiver) = watch::channel(None);
+    let existing = {
+        let mut queries = QUERIES.lock().unwrap();
+        match queries.get(&key) {
+            Some(existing) => Some(existing.clone()),
+            None => {
+                queries.insert(key.clone(), receiver);
+                None
+            }
+        }
+    };
+
+    if let Some(mut existing) = existing {
+        log::debug!("Pergunta idêntica já em andamento, reaproveitando o resultado");
+        // O remetente só some sem resultado quando a primeira chamada foi cancelada
+        return match existing.wait_for(Option::is_some).await {
+            Ok(outcome) => outcome.clone().expect("wait_for garante um resultado"),
+            Err(_) => Err(AppError::Cancelled),
+        };
+    }
+
+    let _entry = Entry { key };
+    let outcome = request.await;
+    sender.send_replace(Some(outcome.clone()));
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_ignores_case_and_surrounding_spaces() {
+        assert_eq!(query_key("  Quantos Pedidos? ", None), query_key("quantos pedidos?", None));
+        assert_ne!(query_key("quantos pedidos?", Some("a")), query_key("quantos pedidos?", Some("b")));
+    }
+}
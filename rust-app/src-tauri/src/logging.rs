@@ -0,0 +1,124 @@
+use crate::error::AppError;
+use crate::i18n::{self, Msg};
+use std::path::Path;
+use std::sync::Mutex;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, Layer, Registry};
+
+// RUST_LOG aceita só o nível ("debug"); sem ele, info
+const LOG_LEVEL_ENV: &str = "RUST_LOG";
+const DEFAULT_LEVEL: LevelFilter = LevelFilter::INFO;
+const LEVELS: [&str; 6] = ["trace", "debug", "info", "warn", "error", "off"];
+
+// Um arquivo por dia em <dados do app>/logs, guardando a última semana
+const LOG_DIR: &str = "logs";
+const LOG_FILE_PREFIX: &str = "rust-app";
+const MAX_LOG_FILES: usize = 7;
+
+static LEVEL_HANDLE: Mutex<Option<reload::Handle<LevelFilter, Registry>>> = Mutex::new(None);
+
+// A gravação em arquivo roda numa thread própria que termina quando o guard é descartado
+static FILE_GUARD: Mutex<Option<WorkerGuard>> = Mutex::new(None);
+
+fn parse_level(level: &str) -> Option<LevelFilter> {
+    level.trim().to_lowercase().parse().ok()
+}
+
+// As chamadas existentes a log:: também chegam aqui, dentro do span do comando
+pub fn init(data_dir: Option<&Path>) {
+    let level = std::env::var(LOG_LEVEL_ENV)
+        .ok()
+        .and_then(|level| parse_level(&level))
+        .unwrap_or(DEFAULT_LEVEL);
+    let (filter, handle) = reload::Layer::new(level);
+
+    let mut file_error = None;
+    let file_layer = data_dir.and_then(|dir| {
+        let log_dir = dir.join(LOG_DIR);
+        let appender = std::fs::create_dir_all(&log_dir)
+            .map_err(|e| e.to_string())
+            .and_then(|_| {
+                RollingFileAppender::builder()
+                    .rotation(Rotation::DAILY)
+                    .filename_prefix(LOG_FILE_PREFIX)
+                    .filename_suffix("log")
+                    .max_log_files(MAX_LOG_FILES)
+                    .build(log_dir)
+                    .map_err(|e| e.to_string())
+            });
+        match appender {
+            Ok(appender) => {
+                let (writer, guard) = tracing_appender::non_blocking(appender);
+                *FILE_GUARD.lock().unwrap() = Some(guard);
+                Some(fmt::layer().with_writer(writer).with_ansi(false).boxed())
+            }
+            Err(e) => {
+                file_error = Some(e);
+                None
+            }
+        }
+    });
+
+    let initialized = tracing_subscriber::registry()
+        .with(filter)
+        // Sem cores também no terminal: os campos do span são formatados uma vez só e
+        // levariam os códigos ANSI para o arquivo
+        .with(fmt::layer().with_writer(std::io::stderr).with_ansi(false))
+        .with(file_layer)
+        .try_init();
+
+    if initialized.is_ok() {
+        *LEVEL_HANDLE.lock().unwrap() = Some(handle);
+        sync_log_level(level);
+    }
+    if let Some(e) = file_error {
+        log::warn!("Logs só no terminal, arquivo indisponível: {}", e);
+    }
+}
+
+// A ponte do crate log filtra pelo próprio nível máximo antes de repassar os registros
+fn sync_log_level(level: LevelFilter) {
+    if let Ok(level) = level.to_string().parse::<log::LevelFilter>() {
+        log::set_max_level(level);
+    }
+}
+
+pub fn set_level(level: &str) -> Result<LevelFilter, AppError> {
+    let Some(level) = parse_level(level) else {
+        return Err(AppError::ValidationError {
+            field: "level".to_string(),
+            expected: i18n::tr(Msg::ExpectedOneOf, &[&LEVELS.join(", ")]),
+        });
+    };
+
+    if let Some(handle) = LEVEL_HANDLE.lock().unwrap().as_ref() {
+        handle
+            .modify(|filter| *filter = level)
+            .map_err(|e| AppError::Io(e.to_string()))?;
+    }
+    sync_log_level(level);
+
+    Ok(level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_names_are_case_insensitive() {
+        assert_eq!(parse_level(" DEBUG "), Some(LevelFilter::DEBUG));
+        assert_eq!(parse_level("off"), Some(LevelFilter::OFF));
+        assert_eq!(parse_level("verboso"), None);
+    }
+
+    #[test]
+    fn unknown_level_is_rejected() {
+        let error = set_level("verboso").unwrap_err();
+        assert!(matches!(error, AppError::ValidationError { ref field, .. } if field == "level"));
+    }
+}
@@ -0,0 +1,134 @@
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::{LazyLock, Mutex};
+use std::time::Instant;
+
+// Amostras mais antigas são descartadas; a contagem total continua crescendo
+const MAX_SAMPLES: usize = 1000;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Command {
+    SendQuery,
+    ConnectDatabase,
+    GetDatabaseStatus,
+}
+
+#[derive(Default)]
+struct Samples {
+    count: u64,
+    durations_ms: VecDeque<u64>,
+}
+
+static SAMPLES: LazyLock<Mutex<HashMap<Command, Samples>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct CommandMetrics {
+    pub count: u64,
+    pub avg_ms: f64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+}
+
+pub type Metrics = HashMap<Command, CommandMetrics>;
+
+// Mede a duração do future, tenha ele terminado com sucesso ou erro
+pub async fn timed<T, F>(command: Command, future: F) -> T
+where
+    F: Future<Output = T>,
+{
+    let started = Instant::now();
+    let result = future.await;
+    record(command, started.elapsed().as_millis() as u64);
+    result
+}
+
+pub fn record(command: Command, duration_ms: u64) {
+    let mut samples = SAMPLES.lock().unwrap();
+    let entry = samples.entry(command).or_default();
+
+    entry.count += 1;
+    entry.durations_ms.push_back(duration_ms);
+    if entry.durations_ms.len() > MAX_SAMPLES {
+        entry.durations_ms.pop_front();
+    }
+}
+
+// Percentil pelo método do posto mais próximo; `sorted` não pode estar vazio
+pub fn percentile(sorted: &[u64], p: f64) -> u64 {
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+pub fn snapshot() -> Metrics {
+    let samples = SAMPLES.lock().unwrap();
+
+    samples
+        .iter()
+        .filter(|(_, entry)| !entry.durations_ms.is_empty())
+        .map(|(command, entry)| {
+            let mut sorted: Vec<u64> = entry.durations_ms.iter().copied().collect();
+            sorted.sort_unstable();
+            let total: u64 = sorted.iter().sum();
+
+            let metrics = CommandMetrics {
+                count: entry.count,
+                avg_ms: total as f64 / sorted.len() as f64,
+                p50_ms: percentile(&sorted, 50.0),
+                p95_ms: percentile(&sorted, 95.0),
+            };
+            (*command, metrics)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[test]
+    fn percentile_by_nearest_rank() {
+        let sorted: Vec<u64> = (1..=20).map(|n| n * 10).collect();
+        assert_eq!(percentile(&sorted, 50.0), 100);
+        assert_eq!(percentile(&sorted, 95.0), 190);
+        assert_eq!(percentile(&sorted, 100.0), 200);
+        assert_eq!(percentile(&sorted, 0.0), 10);
+        assert_eq!(percentile(&[42], 95.0), 42);
+    }
+
+    #[test]
+    fn snapshot_aggregates_synthetic_durations() {
+        let _guard = test_support::serial();
+        SAMPLES.lock().unwrap().clear();
+
+        for duration_ms in [300, 100, 200, 400, 1000] {
+            record(Command::SendQuery, duration_ms);
+        }
+
+        let metrics = snapshot();
+        let send_query = &metrics[&Command::SendQuery];
+        assert_eq!(send_query.count, 5);
+        assert_eq!(send_query.avg_ms, 400.0);
+        assert_eq!(send_query.p50_ms, 300);
+        assert_eq!(send_query.p95_ms, 1000);
+        assert!(!metrics.contains_key(&Command::ConnectDatabase));
+    }
+
+    #[test]
+    fn old_samples_leave_the_ring_buffer() {
+        let _guard = test_support::serial();
+        SAMPLES.lock().unwrap().clear();
+
+        record(Command::GetDatabaseStatus, 5000);
+        for _ in 0..MAX_SAMPLES {
+            record(Command::GetDatabaseStatus, 10);
+        }
+
+        let status = &snapshot()[&Command::GetDatabaseStatus];
+        assert_eq!(status.count, MAX_SAMPLES as u64 + 1);
+        assert_eq!(status.p95_ms, 10);
+    }
+}
@@ -0,0 +1,69 @@
+use crate::error::AppError;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+// Desligado por padrão: guardar perguntas para depois só faz sentido se o usuário pediu
+static ENABLED: Mutex<bool> = Mutex::new(false);
+
+// Perguntas que falharam por falta de rede, na ordem em que foram feitas. Fica só em
+// memória; fechar o app descarta a fila
+static PENDING: Mutex<VecDeque<PendingQuestion>> = Mutex::new(VecDeque::new());
+
+#[derive(Serialize, Debug, Clone)]
+pub struct PendingQuestion {
+    pub question: String,
+    pub connection_id: Option<String>,
+    pub queued_at: String,
+}
+
+pub fn set_enabled(enabled: bool) {
+    *ENABLED.lock().unwrap() = enabled;
+}
+
+// Só falhas em que o backend não foi alcançado; erros do próprio backend não se
+// resolvem repetindo a pergunta mais tarde
+pub fn is_offline_error(err: &AppError) -> bool {
+    matches!(
+        err,
+        AppError::Network(_) | AppError::BackendUnavailable | AppError::HostUnreachable(_)
+    )
+}
+
+// Retorna a pergunta enfileirada, ou None se a fila estiver desligada
+pub fn enqueue(question: &str, connection_id: Option<String>) -> Option<PendingQuestion> {
+    if !*ENABLED.lock().unwrap() {
+        return None;
+    }
+
+    let pending = PendingQuestion {
+        question: question.to_string(),
+        connection_id,
+        queued_at: chrono::Utc::now().to_rfc3339(),
+    };
+    PENDING.lock().unwrap().push_back(pending.clone());
+    Some(pending)
+}
+
+pub fn take_all() -> Vec<PendingQuestion> {
+    PENDING.lock().unwrap().drain(..).collect()
+}
+
+// Devolve ao início da fila o que não pôde ser reenviado, mantendo a ordem original
+pub fn restore(questions: Vec<PendingQuestion>) {
+    let mut pending = PENDING.lock().unwrap();
+    for question in questions.into_iter().rev() {
+        pending.push_front(question);
+    }
+}
+
+pub fn list() -> Vec<PendingQuestion> {
+    PENDING.lock().unwrap().iter().cloned().collect()
+}
+
+pub fn clear() -> usize {
+    let mut pending = PENDING.lock().unwrap();
+    let count = pending.len();
+    pending.clear();
+    count
+}
@@ -0,0 +1,109 @@
+use crate::error::AppError;
+use crate::redaction;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+const PROFILES_FILE: &str = "connection_profiles.json";
+
+// Na ordem em que foram salvos
+static PROFILES: Mutex<Vec<Profile>> = Mutex::new(Vec::new());
+
+// Configuração de conexão reaproveitável. Campos sensíveis nunca são gravados:
+// só o nome deles fica em `secret_fields`, para a interface saber o que pedir
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Profile {
+    pub name: String,
+    pub driver_type: String,
+    pub config: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub secret_fields: Vec<String>,
+    pub saved_at: String,
+}
+
+pub fn load() {
+    *PROFILES.lock().unwrap() = storage::load(PROFILES_FILE);
+}
+
+// Salvar com um nome existente substitui o perfil anterior
+pub fn save(
+    name: &str,
+    driver_type: &str,
+    config: &HashMap<String, serde_json::Value>,
+) -> Result<Profile, AppError> {
+    let name = name.trim();
+
+    let mut secret_fields: Vec<String> = config
+        .keys()
+        .filter(|key| redaction::is_sensitive_key(key))
+        .cloned()
+        .collect();
+    secret_fields.sort();
+
+    let profile = Profile {
+        name: name.to_string(),
+        driver_type: driver_type.to_string(),
+        config: config
+            .iter()
+            .filter(|(key, _)| !redaction::is_sensitive_key(key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect(),
+        secret_fields,
+        saved_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    let mut profiles = PROFILES.lock().unwrap();
+    match profiles.iter_mut().find(|existing| existing.name == name) {
+        Some(existing) => *existing = profile.clone(),
+        None => profiles.push(profile.clone()),
+    }
+    storage::save(PROFILES_FILE, &*profiles)?;
+
+    Ok(profile)
+}
+
+pub fn list() -> Vec<Profile> {
+    PROFILES.lock().unwrap().clone()
+}
+
+pub fn get(name: &str) -> Result<Profile, AppError> {
+    PROFILES
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|profile| profile.name == name)
+        .cloned()
+        .ok_or_else(|| AppError::NotFound(name.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+    use serde_json::json;
+
+    #[test]
+    fn passwords_are_never_persisted() {
+        let _guard = test_support::serial();
+        let dir = test_support::temp_dir();
+        storage::set_data_dir(dir.clone());
+        load();
+
+        let config = HashMap::from([
+            ("host".to_string(), json!("db.local")),
+            ("password".to_string(), json!("s3nha-secreta")),
+            ("api_token".to_string(), json!("tok-123")),
+        ]);
+        let profile = save("produção", "postgresql", &config).unwrap();
+
+        assert_eq!(profile.secret_fields, ["api_token", "password"]);
+        assert!(!profile.config.contains_key("password"));
+        let file = std::fs::read_to_string(dir.join(PROFILES_FILE)).unwrap();
+        assert!(!file.contains("s3nha-secreta") && !file.contains("tok-123"), "{}", file);
+        assert!(file.contains("db.local"));
+
+        load();
+        assert_eq!(get("produção").unwrap().config.len(), 1);
+    }
+}
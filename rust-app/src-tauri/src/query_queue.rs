@@ -0,0 +1,110 @@
+use crate::error::AppError;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+const DEFAULT_MAX_CONCURRENT: usize = 1;
+
+// Consultas rodando mais as que aguardam na fila. Acima disso a nova consulta é
+// recusada, em vez de esperar indefinidamente atrás de cliques repetidos
+const MAX_PENDING: usize = 8;
+
+static SEMAPHORE: LazyLock<Mutex<Arc<Semaphore>>> =
+    LazyLock::new(|| Mutex::new(Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT))));
+
+static PENDING: AtomicUsize = AtomicUsize::new(0);
+
+// Liberado ao descartar, inclusive quando a consulta é cancelada ainda na fila
+struct Pending;
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        PENDING.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub struct QueryPermit {
+    _permit: OwnedSemaphorePermit,
+    _pending: Pending,
+}
+
+pub async fn acquire() -> Result<QueryPermit, AppError> {
+    if PENDING.fetch_add(1, Ordering::SeqCst) >= MAX_PENDING {
+        PENDING.fetch_sub(1, Ordering::SeqCst);
+        return Err(AppError::TooManyPending);
+    }
+    let pending = Pending;
+
+    let semaphore = SEMAPHORE.lock().unwrap().clone();
+    let permit = semaphore
+        .acquire_owned()
+        .await
+        .expect("query semaphore is never closed");
+
+    Ok(QueryPermit {
+        _permit: permit,
+        _pending: pending,
+    })
+}
+
+// Consultas já em andamento terminam com o limite antigo; as próximas usam o novo
+pub fn set_max_concurrent(n: usize) -> usize {
+    let n = n.max(1);
+    *SEMAPHORE.lock().unwrap() = Arc::new(Semaphore::new(n));
+    n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn concurrent_queries_run_one_at_a_time() {
+        let _guard = test_support::serial_async().await;
+        set_max_concurrent(DEFAULT_MAX_CONCURRENT);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let queries: Vec<_> = (0..4)
+            .map(|_| {
+                let running = running.clone();
+                let peak = peak.clone();
+                tokio::spawn(async move {
+                    let _permit = acquire().await.unwrap();
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for query in queries {
+            query.await.unwrap();
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+        assert_eq!(PENDING.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn full_queue_rejects_new_queries() {
+        let _guard = test_support::serial_async().await;
+        set_max_concurrent(DEFAULT_MAX_CONCURRENT);
+
+        let running = acquire().await.unwrap();
+        let waiting: Vec<_> = (1..MAX_PENDING).map(|_| tokio::spawn(acquire())).collect();
+        while PENDING.load(Ordering::SeqCst) < MAX_PENDING {
+            tokio::task::yield_now().await;
+        }
+
+        assert!(matches!(acquire().await, Err(AppError::TooManyPending)));
+
+        drop(running);
+        for query in waiting {
+            assert!(query.await.unwrap().is_ok());
+        }
+        assert_eq!(PENDING.load(Ordering::SeqCst), 0);
+    }
+}
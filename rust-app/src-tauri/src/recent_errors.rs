@@ -0,0 +1,41 @@
+use crate::error::AppError;
+use crate::redaction;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+// Suficiente para o painel de diagnóstico; os mais antigos são descartados
+const MAX_ERRORS: usize = 50;
+
+// Do mais recente para o mais antigo
+static ERRORS: Mutex<VecDeque<ErrorRecord>> = Mutex::new(VecDeque::new());
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ErrorRecord {
+    pub code: String,
+    pub message: String,
+    pub timestamp: String,
+    pub command: String,
+    pub request_id: String,
+}
+
+// A mensagem pode ecoar a configuração da conexão, então passa pela mesma redação do histórico
+pub fn record(command: &str, request_id: &str, error: &AppError) {
+    let mut errors = ERRORS.lock().unwrap();
+    errors.push_front(ErrorRecord {
+        code: error.code().to_string(),
+        message: redaction::redact_response_text(&error.message()),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        command: command.to_string(),
+        request_id: request_id.to_string(),
+    });
+    errors.truncate(MAX_ERRORS);
+}
+
+pub fn list() -> Vec<ErrorRecord> {
+    ERRORS.lock().unwrap().iter().cloned().collect()
+}
+
+pub fn clear() {
+    ERRORS.lock().unwrap().clear();
+}
@@ -0,0 +1,241 @@
+use crate::error::AppError;
+use crate::recent_errors;
+use std::future::Future;
+use tracing::Instrument;
+
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+    static COMMAND: String;
+}
+
+pub fn current() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+pub fn current_command() -> Option<String> {
+    COMMAND.try_with(|command| command.clone()).ok()
+}
+
+pub fn attach(builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    match current() {
+        Some(id) => builder.header(REQUEST_ID_HEADER, id),
+        None => builder,
+    }
+}
+
+// Executa um comando com um id próprio, enviado em todas as requisições dele e
+// anexado ao erro para o usuário citar ao reportar o problema. Comandos chamados
+// por outros reaproveitam o id de quem chamou
+pub async fn scope<T, F>(command: &str, future: F) -> Result<T, AppError>
+where
+    F: Future<Output = Result<T, AppError>>,
+{
+    if current().is_some() {
+        return future.await;
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let span = tracing::info_span!("command", name = command, request_id = %id);
+    let started = std::time::Instant::now();
+    span.in_scope(|| tracing::debug!("iniciado"));
+
+    let future = COMMAND.scope(command.to_string(), future);
+    let result = REQUEST_ID.scope(id.clone(), future).instrument(span.clone()).await;
+
+    let _entered = span.enter();
+    finish(command, id, started, result)
+}
+
+// Para comandos síncronos (favoritos, exportação, configurações), que não fazem
+// requisições e por isso não precisam do id nas tarefas
+pub fn scope_sync<T>(command: &str, run: impl FnOnce() -> Result<T, AppError>) -> Result<T, AppError> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let span = tracing::info_span!("command", name = command, request_id = %id);
+    let _entered = span.enter();
+    let started = std::time::Instant::now();
+    tracing::debug!("iniciado");
+
+    finish(command, id, started, run())
+}
+
+fn finish<T>(
+    command: &str,
+    id: String,
+    started: std::time::Instant,
+    result: Result<T, AppError>,
+) -> Result<T, AppError> {
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    match result {
+        Ok(value) => {
+            tracing::debug!(elapsed_ms, "concluído");
+            Ok(value)
+        }
+        // Pedido de confirmação é parte do fluxo normal de execute_sql, não uma falha
+        Err(e @ AppError::ConfirmationRequired { .. }) => {
+            tracing::debug!(elapsed_ms, "aguardando confirmação");
+            Err(e.with_request_id(id))
+        }
+        Err(e) => {
+            tracing::error!(elapsed_ms, code = e.code(), "falhou: {}", e);
+            recent_errors::record(command, &id, &e);
+            Err(e.with_request_id(id))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_client;
+    use crate::test_support::{self, MockServer, Response};
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id};
+    use tracing::{Event, Level, Subscriber};
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::Layer;
+
+    // Spans e eventos emitidos, como "INFO command name=... request_id=..."
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<(Level, String)>>>);
+
+    #[derive(Default)]
+    struct Fields(Vec<String>);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0.push(format!("{}={:?}", field.name(), value));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.push(format!("{}={}", field.name(), value));
+        }
+    }
+
+    impl<S: Subscriber> Layer<S> for Capture {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _: &Id, _: Context<'_, S>) {
+            let mut fields = Fields::default();
+            attrs.record(&mut fields);
+            let line = format!("{} {}", attrs.metadata().name(), fields.0.join(" "));
+            self.0.lock().unwrap().push((*attrs.metadata().level(), line));
+        }
+
+        fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+            let mut fields = Fields::default();
+            event.record(&mut fields);
+            self.0.lock().unwrap().push((*event.metadata().level(), fields.0.join(" ")));
+        }
+    }
+
+    fn captured<T>(run: impl FnOnce() -> T) -> (T, Vec<(Level, String)>) {
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        let result = tracing::subscriber::with_default(subscriber, run);
+        let records = capture.0.lock().unwrap().clone();
+        (result, records)
+    }
+
+    fn traced_id(error: &AppError) -> String {
+        match error {
+            AppError::Traced { request_id, .. } => request_id.clone(),
+            other => panic!("erro sem request_id: {:?}", other),
+        }
+    }
+
+    fn recorded(request_id: &str) -> Option<recent_errors::ErrorRecord> {
+        recent_errors::list().into_iter().find(|record| record.request_id == request_id)
+    }
+
+    #[test]
+    fn failure_is_recorded_under_its_command() {
+        let _guard = test_support::serial();
+        let error = scope_sync::<()>("save_favorite", || Err(AppError::NotFound("x".into()))).unwrap_err();
+        let record = recorded(&traced_id(&error)).expect("erro não registrado");
+        assert_eq!(record.command, "save_favorite");
+        assert_eq!(record.code, "not_found");
+    }
+
+    #[test]
+    fn confirmation_is_not_recorded() {
+        let _guard = test_support::serial();
+        let error = scope_sync::<()>("execute_sql", || {
+            Err(AppError::ConfirmationRequired {
+                verb: "DELETE".into(),
+                token: "t".into(),
+            })
+        })
+        .unwrap_err();
+        assert_eq!(error.code(), "confirmation_required");
+        assert!(recorded(&traced_id(&error)).is_none());
+    }
+
+    #[tokio::test]
+    async fn nested_scope_keeps_the_outer_command() {
+        let _guard = test_support::serial_async().await;
+        let error = scope::<(), _>("send_query", async {
+            scope("send_query_structured", async { Err(AppError::Timeout) }).await
+        })
+        .await
+        .unwrap_err();
+        let record = recorded(&traced_id(&error)).expect("erro não registrado");
+        assert_eq!(record.command, "send_query");
+    }
+
+    #[tokio::test]
+    async fn header_matches_the_id_in_the_error() {
+        let _guard = test_support::serial_async().await;
+        let server = MockServer::start(|_, _| Response::new(500, "text/plain", "erro interno"));
+        test_support::use_backend(&server.url());
+
+        let query = || scope("send_query", async { api_client::send_query("total", None).await.map(|_| ()) });
+        let first = query().await.unwrap_err();
+        let second = query().await.unwrap_err();
+
+        let requests = server.requests();
+        assert_eq!(requests[0].header("x-request-id"), Some(traced_id(&first).as_str()));
+        assert_eq!(requests[1].header("x-request-id"), Some(traced_id(&second).as_str()));
+        assert_ne!(traced_id(&first), traced_id(&second));
+    }
+
+    #[test]
+    fn command_span_carries_the_request_id() {
+        let (result, records) = captured(|| scope_sync("set_language", || Ok(())));
+        assert!(result.is_ok());
+
+        let (level, span) = &records[0];
+        assert_eq!(*level, Level::INFO);
+        assert!(span.starts_with("command name=set_language request_id="), "{}", span);
+        assert_eq!(records[1], (Level::DEBUG, "message=iniciado".to_string()));
+        assert!(records[2].0 == Level::DEBUG && records[2].1.contains("concluído"), "{:?}", records);
+    }
+
+    #[test]
+    fn failures_are_logged_as_errors() {
+        let (result, records) = captured(|| scope_sync::<()>("save_favorite", || Err(AppError::NotFound("x".into()))));
+        let id = traced_id(&result.unwrap_err());
+
+        assert!(records[0].1.ends_with(&format!("request_id={}", id)), "{:?}", records);
+        assert!(records.iter().any(|(level, _)| *level == Level::ERROR), "{:?}", records);
+    }
+
+    #[test]
+    fn recent_errors_come_newest_first() {
+        let _guard = test_support::serial();
+        recent_errors::clear();
+
+        let first = crate::set_log_level("barulhento".into()).unwrap_err();
+        let second = crate::diff_results(Vec::new(), Vec::new(), " ".into()).unwrap_err();
+
+        let ours = [traced_id(&first), traced_id(&second)];
+        let records: Vec<_> = recent_errors::list()
+            .into_iter()
+            .filter(|record| ours.contains(&record.request_id))
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!((records[0].command.as_str(), records[0].request_id.as_str()), ("diff_results", ours[1].as_str()));
+        assert_eq!((records[1].command.as_str(), records[1].request_id.as_str()), ("set_log_level", ours[0].as_str()));
+        assert_eq!(records[0].code, "validation_error");
+    }
+}
@@ -1,4 +1,579 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
+use unicode_normalization::UnicodeNormalization;
+
+const DEFAULT_BLACKLIST: [&str; 4] = ["DROP", "DELETE", "INSERT", "UPDATE"];
+
+// No modo somente leitura qualquer verbo de escrita ou DDL é bloqueado,
+// independente da lista configurada
+const STRICT_READ_ONLY_KEYWORDS: [&str; 20] = [
+    "INSERT", "UPDATE", "DELETE", "DROP", "ALTER", "CREATE", "GRANT", "REVOKE", "TRUNCATE",
+    "MERGE", "REPLACE", "UPSERT", "RENAME", "EXEC", "EXECUTE", "CALL", "ATTACH", "DETACH",
+    "VACUUM", "REINDEX",
+];
+
+// Perguntas longas demais (texto colado por engano) só geram timeouts na IA
+const DEFAULT_MAX_LENGTH: usize = 2000;
+
+// Sequências de comentário SQL ("--", "#" e "/* */") não têm uso legítimo numa pergunta
+const COMMENT_PATTERNS: [&str; 3] = ["--", "#", r"/\*"];
+
+// UNION seguido de SELECT (com qualquer espaço ou quebra de linha entre eles) é a forma
+// clássica de extrair dados de outras tabelas
+const UNION_SELECT_PATTERN: &str = r"\bunion\s+select\b";
+
+// Caracteres invisíveis usados para esconder palavras-chave ("DEL\u{200B}ETE")
+const ZERO_WIDTH_CHARS: [char; 4] = ['\u{200B}', '\u{200C}', '\u{200D}', '\u{FEFF}'];
+
+// Caminho do log de auditoria; sem a variável, usa o diretório de dados do app
+const AUDIT_LOG_ENV: &str = "RUST_APP_AUDIT_LOG";
+const AUDIT_LOG_FILE: &str = "security_audit.jsonl";
+
+static SECURITY_CONFIG: LazyLock<Mutex<SecurityConfig>> =
+    LazyLock::new(|| Mutex::new(SecurityConfig::default()));
+
+// O Mutex garante que comandos concorrentes não intercalem linhas no arquivo
+static AUDIT_LOG: LazyLock<Mutex<AuditLog>> = LazyLock::new(|| Mutex::new(AuditLog::default()));
+
+#[derive(Default)]
+struct AuditLog {
+    default_dir: Option<PathBuf>,
+    file: Option<File>,
+}
+
+impl AuditLog {
+    fn path(&self) -> Option<PathBuf> {
+        match std::env::var(AUDIT_LOG_ENV) {
+            Ok(path) if !path.is_empty() => Some(PathBuf::from(path)),
+            _ => self.default_dir.as_ref().map(|dir| dir.join(AUDIT_LOG_FILE)),
+        }
+    }
+
+    fn append(&mut self, line: &str) -> std::io::Result<()> {
+        if self.file.is_none() {
+            let Some(path) = self.path() else {
+                return Ok(());
+            };
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            self.file = Some(OpenOptions::new().create(true).append(true).open(path)?);
+        }
+
+        let file = self.file.as_mut().unwrap();
+        writeln!(file, "{}", line)?;
+        file.flush()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityMode {
+    #[default]
+    Blacklist,
+    StrictReadOnly,
+}
+
+pub struct SecurityConfig {
+    keywords: Vec<String>,
+    mode: SecurityMode,
+    // Trava pedida pelo usuário para a sessão atual; vale por cima do modo configurado
+    read_only: bool,
+    max_length: usize,
+    blocked_regex: Regex,
+}
+
+impl SecurityConfig {
+    pub fn new(keywords: Vec<String>) -> Self {
+        let mut config = SecurityConfig {
+            keywords: Vec::new(),
+            mode: SecurityMode::default(),
+            read_only: false,
+            max_length: DEFAULT_MAX_LENGTH,
+            blocked_regex: build_blocked_regex(&[]),
+        };
+        config.set_keywords(keywords);
+        config
+    }
+
+    pub fn keywords(&self) -> &[String] {
+        &self.keywords
+    }
+
+    pub fn set_keywords(&mut self, keywords: Vec<String>) {
+        self.keywords = keywords
+            .iter()
+            .map(|word| word.trim().to_uppercase())
+            .filter(|word| !word.is_empty())
+            .collect();
+        self.rebuild_regex();
+    }
+
+    pub fn mode(&self) -> SecurityMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: SecurityMode) {
+        self.mode = mode;
+        self.rebuild_regex();
+    }
+
+    pub fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn set_read_only(&mut self, enabled: bool) {
+        self.read_only = enabled;
+        self.rebuild_regex();
+    }
+
+    fn rebuild_regex(&mut self) {
+        let mut keywords = self.keywords.clone();
+        if self.mode == SecurityMode::StrictReadOnly || self.read_only {
+            for word in STRICT_READ_ONLY_KEYWORDS {
+                if !keywords.iter().any(|existing| existing == word) {
+                    keywords.push(word.to_string());
+                }
+            }
+        }
+        self.blocked_regex = build_blocked_regex(&keywords);
+    }
+
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    pub fn set_max_length(&mut self, max_length: usize) {
+        self.max_length = max_length;
+    }
+}
+
+impl Default for SecurityConfig {
+    fn default() -> Self {
+        SecurityConfig::new(DEFAULT_BLACKLIST.iter().map(|word| word.to_string()).collect())
+    }
+}
+
+// Uma única varredura cobre palavras-chave (apenas palavras inteiras, assim
+// "updated_at" ou "deletion" não são bloqueados), UNION SELECT e comentários
+fn build_blocked_regex(keywords: &[String]) -> Regex {
+    let mut pattern = String::from("(?i)");
+    if !keywords.is_empty() {
+        let escaped: Vec<String> = keywords.iter().map(|word| regex::escape(word)).collect();
+        pattern.push_str(&format!(r"\b(?P<keyword>{})\b|", escaped.join("|")));
+    }
+    pattern.push_str(&format!("(?P<union>{})|", UNION_SELECT_PATTERN));
+    pattern.push_str(&COMMENT_PATTERNS.join("|"));
+
+    Regex::new(&pattern).unwrap()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecurityViolation {
+    BlacklistedKeyword(String),
+    CommentSequence,
+    UnionSelect,
+    StackedStatements,
+    TooLong(usize),
+}
+
+impl SecurityViolation {
+    pub fn rule(&self) -> &'static str {
+        match self {
+            SecurityViolation::BlacklistedKeyword(_) => "blacklisted_keyword",
+            SecurityViolation::CommentSequence => "comment_sequence",
+            SecurityViolation::UnionSelect => "union_select",
+            SecurityViolation::StackedStatements => "stacked_statements",
+            SecurityViolation::TooLong(_) => "too_long",
+        }
+    }
+}
+
+pub fn set_keywords(keywords: Vec<String>) {
+    SECURITY_CONFIG.lock().unwrap().set_keywords(keywords);
+}
+
+pub fn set_mode(mode: SecurityMode) {
+    SECURITY_CONFIG.lock().unwrap().set_mode(mode);
+}
+
+pub fn current_mode() -> SecurityMode {
+    SECURITY_CONFIG.lock().unwrap().mode()
+}
+
+pub fn set_read_only(enabled: bool) {
+    SECURITY_CONFIG.lock().unwrap().set_read_only(enabled);
+}
+
+pub fn is_read_only() -> bool {
+    SECURITY_CONFIG.lock().unwrap().read_only()
+}
+
+pub fn set_max_length(max_length: usize) {
+    SECURITY_CONFIG.lock().unwrap().set_max_length(max_length);
+}
+
+pub fn current_keywords() -> Vec<String> {
+    SECURITY_CONFIG.lock().unwrap().keywords().to_vec()
+}
+
+#[cfg(test)]
+pub fn reset_config() {
+    *SECURITY_CONFIG.lock().unwrap() = SecurityConfig::default();
+}
+
+// Um ';' seguido de mais conteúdo indica instruções empilhadas ("SELECT 1; DROP ...").
+// O ';' final é tolerado, e ';' dentro de aspas simples ou duplas fechadas é tratado
+// como texto literal, já que faz parte de um valor e não separa instruções. Aspas sem
+// fechamento (como o apóstrofo em "customer's") não protegem o restante da entrada
+fn has_stacked_statements(input: &str) -> bool {
+    let mut rest = input;
+
+    while let Some(pos) = rest.find(['\'', '"', ';']) {
+        let c = rest[pos..].chars().next().unwrap();
+        let after = &rest[pos + 1..];
+
+        if c == ';' {
+            if after.chars().any(|ch| !ch.is_whitespace()) {
+                return true;
+            }
+            rest = after;
+        } else {
+            rest = match after.find(c) {
+                Some(close) => &after[close + 1..],
+                None => after,
+            };
+        }
+    }
+
+    false
+}
+
+pub fn set_audit_dir(dir: PathBuf) {
+    let mut audit = AUDIT_LOG.lock().unwrap();
+    audit.default_dir = Some(dir);
+    audit.file = None;
+}
+
+fn audit_violation(input: &str, violation: &SecurityViolation) {
+    let mut rule = violation.rule().to_string();
+    if let SecurityViolation::BlacklistedKeyword(word) = violation {
+        rule = format!("{}:{}", rule, word);
+    }
+
+    let line = serde_json::json!({
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "input": input,
+        "rule": rule,
+    })
+    .to_string();
+
+    // Falha ao gravar a auditoria não deve impedir o bloqueio da entrada
+    if let Err(e) = AUDIT_LOG.lock().unwrap().append(&line) {
+        log::error!("Erro ao gravar log de auditoria: {}", e);
+    }
+}
+
+pub fn validate_input_detailed(input: &str) -> Result<(), SecurityViolation> {
+    let result = check_input(input);
+    if let Err(violation) = &result {
+        audit_violation(input, violation);
+    }
+    result
+}
+
+// Remove caracteres de largura zero e aplica NFKC, que converte letras latinas de
+// largura total ("ＤＲＯＰ") para ASCII sem alterar acentos do português
+pub fn normalize(input: &str) -> String {
+    input
+        .chars()
+        .filter(|c| !ZERO_WIDTH_CHARS.contains(c))
+        .nfkc()
+        .collect()
+}
+
+// Mesmas regras sem gravar na auditoria, para pré-visualizações enquanto o usuário digita:
+// só o que é de fato enviado e recusado deve ir para o log
+pub fn check_input(input: &str) -> Result<(), SecurityViolation> {
+    let input = normalize(input);
+    let input = input.as_str();
+    let config = SECURITY_CONFIG.lock().unwrap();
+
+    // Conta caracteres Unicode e não bytes, para que acentos não reduzam o limite
+    if input.chars().count() > config.max_length() {
+        return Err(SecurityViolation::TooLong(config.max_length()));
+    }
+
+    if let Some(caps) = config.blocked_regex.captures(input) {
+        if let Some(keyword) = caps.name("keyword") {
+            return Err(SecurityViolation::BlacklistedKeyword(
+                keyword.as_str().to_uppercase(),
+            ));
+        }
+        if caps.name("union").is_some() {
+            return Err(SecurityViolation::UnionSelect);
+        }
+        return Err(SecurityViolation::CommentSequence);
+    }
+
+    if has_stacked_statements(input) {
+        return Err(SecurityViolation::StackedStatements);
+    }
+
+    Ok(())
+}
+
+// SQL sem comentários e com o conteúdo dos literais trocado por um espaço, para
+// palavras e parênteses dentro deles não contarem
+fn code_only(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' => {
+                for next in chars.by_ref() {
+                    if next == c {
+                        break;
+                    }
+                }
+                out.push(' ');
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+                out.push(' ');
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for next in chars.by_ref() {
+                    if prev == '*' && next == '/' {
+                        break;
+                    }
+                    prev = next;
+                }
+                out.push(' ');
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
+fn first_word(text: &str) -> String {
+    text.trim_start_matches(|c: char| c.is_whitespace() || c == '(')
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect::<String>()
+        .to_uppercase()
+}
+
+// Verbos que abrem a instrução e, depois de um WITH, cada CTE e a instrução principal.
+// Só essas posições contam: REPLACE() ou uma coluna "update" num SELECT não alteram nada
+fn statement_verbs(sql: &str) -> Vec<String> {
+    let code = code_only(sql);
+    let first = first_word(&code);
+    if first != "WITH" {
+        return vec![first];
+    }
+
+    let mut verbs = Vec::new();
+    let mut depth = 0usize;
+    for (i, c) in code.char_indices() {
+        match c {
+            '(' => {
+                // Corpo do CTE, não a lista de colunas dele
+                let before = code[..i].trim_end().rsplit([' ', '\t', '\n', '\r']).next().unwrap_or_default();
+                if depth == 0 && (before.eq_ignore_ascii_case("AS") || before.eq_ignore_ascii_case("MATERIALIZED")) {
+                    verbs.push(first_word(&code[i + 1..]));
+                }
+                depth += 1;
+            }
+            ')' => {
+                depth = depth.saturating_sub(1);
+                let after = code[i + 1..].trim_start();
+                // Vírgula leva ao próximo CTE e AS vem depois da lista de colunas dele
+                if depth == 0 && !after.starts_with(',') && first_word(after) != "AS" {
+                    verbs.push(first_word(after));
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    verbs
+}
+
+// Verbo de escrita ou DDL que abre a instrução (ou algum CTE dela), em maiúsculas
+pub fn destructive_verb(sql: &str) -> Option<String> {
+    statement_verbs(&normalize(sql))
+        .into_iter()
+        .find(|verb| STRICT_READ_ONLY_KEYWORDS.contains(&verb.as_str()))
+}
+
+// Mantida por compatibilidade com quem só precisa do resultado booleano
 pub fn validate_input(input: &str) -> bool {
-    let blacklist = ["DROP", "DELETE", "INSERT", "UPDATE"];
-    !blacklist.iter().any(|&word| input.to_lowercase().contains(&word.to_lowercase()))
-}
\ No newline at end of file
+    validate_input_detailed(input).is_ok()
+}
+
+// Nomes de tabela vão para a URL do backend, então só letras, dígitos, "_" e "."
+// (para esquemas, como "public.users") são aceitos, além das regras acima
+pub fn validate_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    let valid_start = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+
+    valid_start
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+        && validate_input(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, SerialGuard};
+
+    // Volta à configuração padrão; a trava fica com o teste até o fim
+    fn default_config() -> SerialGuard {
+        let guard = test_support::serial();
+        reset_config();
+        guard
+    }
+
+    #[test]
+    fn keyword_inside_identifier_is_allowed() {
+        let _guard = default_config();
+        assert_eq!(check_input("quando foi o updated_at mais recente?"), Ok(()));
+        assert_eq!(check_input("qual a deletion policy?"), Ok(()));
+    }
+
+    #[test]
+    fn whole_keyword_is_blocked() {
+        let _guard = default_config();
+        assert_eq!(
+            check_input("DROP TABLE users"),
+            Err(SecurityViolation::BlacklistedKeyword("DROP".into()))
+        );
+    }
+
+    #[test]
+    fn comment_sequences_are_blocked() {
+        let _guard = default_config();
+        for input in ["vendas --", "vendas # total", "vendas /* x */", "total de vendas -- por mês"] {
+            assert_eq!(check_input(input), Err(SecurityViolation::CommentSequence), "{}", input);
+        }
+    }
+
+    #[test]
+    fn custom_keywords_replace_the_default_list() {
+        let _guard = default_config();
+        set_keywords(vec!["truncate".into()]);
+        assert_eq!(
+            check_input("truncate pedidos"),
+            Err(SecurityViolation::BlacklistedKeyword("TRUNCATE".into()))
+        );
+        assert_eq!(check_input("drop pedidos"), Ok(()));
+        assert_eq!(current_keywords(), vec!["TRUNCATE".to_string()]);
+    }
+
+    #[test]
+    fn stacked_statements_are_blocked() {
+        let _guard = default_config();
+        assert_eq!(check_input("SELECT 1; SELECT 2"), Err(SecurityViolation::StackedStatements));
+        assert_eq!(check_input("SELECT 1;"), Ok(()));
+        // Dentro de aspas fechadas o ';' é texto, não separador
+        assert_eq!(check_input("clientes com nome 'a;b'"), Ok(()));
+    }
+
+    #[test]
+    fn length_limit_boundary() {
+        let _guard = default_config();
+        assert_eq!(check_input(&"a".repeat(2000)), Ok(()));
+        assert_eq!(check_input(&"a".repeat(2001)), Err(SecurityViolation::TooLong(2000)));
+        // Conta caracteres, não bytes
+        assert_eq!(check_input(&"ç".repeat(2000)), Ok(()));
+    }
+
+    #[test]
+    fn union_select_is_blocked() {
+        let _guard = default_config();
+        assert_eq!(check_input("1 UNION SELECT senha"), Err(SecurityViolation::UnionSelect));
+        assert_eq!(check_input("1 union   select senha"), Err(SecurityViolation::UnionSelect));
+        assert_eq!(check_input("in this reunion we select winners"), Ok(()));
+    }
+
+    #[test]
+    fn blocked_input_is_audited() {
+        let _guard = default_config();
+        let dir = test_support::temp_dir();
+        set_audit_dir(dir.clone());
+
+        assert!(!validate_input("DROP TABLE users"));
+        assert!(validate_input("total de vendas"));
+
+        let log = fs::read_to_string(dir.join(AUDIT_LOG_FILE)).unwrap();
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let entry: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(entry["rule"], "blacklisted_keyword:DROP");
+        assert_eq!(entry["input"], "DROP TABLE users");
+    }
+
+    #[test]
+    fn strict_mode_blocks_every_write_verb() {
+        let _guard = default_config();
+        assert_eq!(check_input("alter table pedidos"), Ok(()));
+
+        set_mode(SecurityMode::StrictReadOnly);
+        assert_eq!(
+            check_input("alter table pedidos"),
+            Err(SecurityViolation::BlacklistedKeyword("ALTER".into()))
+        );
+        assert_eq!(check_input("total de pedidos"), Ok(()));
+    }
+
+    #[test]
+    fn hidden_keywords_are_caught() {
+        let _guard = default_config();
+        assert_eq!(
+            check_input("DEL\u{200B}ETE FROM users"),
+            Err(SecurityViolation::BlacklistedKeyword("DELETE".into()))
+        );
+        assert_eq!(
+            check_input("ＤＲＯＰ TABLE users"),
+            Err(SecurityViolation::BlacklistedKeyword("DROP".into()))
+        );
+        assert_eq!(normalize("ação"), "ação");
+    }
+
+    #[test]
+    fn destructive_verb_only_looks_at_the_leading_verb() {
+        assert_eq!(destructive_verb("SELECT REPLACE(nome, 'a', 'b') FROM clientes"), None);
+        assert_eq!(destructive_verb("SELECT updated_at, 'DELETE' FROM pedidos"), None);
+        assert_eq!(destructive_verb("  delete from pedidos where id = 1").as_deref(), Some("DELETE"));
+        assert_eq!(destructive_verb("/* limpeza */ -- antiga\nTRUNCATE pedidos").as_deref(), Some("TRUNCATE"));
+    }
+
+    #[test]
+    fn destructive_verb_looks_past_the_with_clause() {
+        let read = "WITH recentes (id, replace) AS (SELECT id, total FROM pedidos), caros AS (SELECT * FROM recentes) SELECT * FROM caros";
+        assert_eq!(destructive_verb(read), None);
+
+        let write = "WITH antigos AS (SELECT id FROM pedidos WHERE ano < 2020) DELETE FROM pedidos WHERE id IN (SELECT id FROM antigos)";
+        assert_eq!(destructive_verb(write).as_deref(), Some("DELETE"));
+
+        let cte_write = "WITH apagados AS (DELETE FROM pedidos RETURNING id) SELECT count(*) FROM apagados";
+        assert_eq!(destructive_verb(cte_write).as_deref(), Some("DELETE"));
+    }
+}
@@ -0,0 +1,30 @@
+use crate::error::AppError;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+const SETTINGS_FILE: &str = "settings.json";
+
+static SETTINGS: Mutex<Settings> = Mutex::new(Settings { default_driver: None });
+
+// Preferências do usuário que sobrevivem entre sessões
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Settings {
+    // Driver já selecionado ao abrir o diálogo de conexão
+    pub default_driver: Option<String>,
+}
+
+pub fn load() {
+    *SETTINGS.lock().unwrap() = storage::load(SETTINGS_FILE);
+}
+
+pub fn default_driver() -> Option<String> {
+    SETTINGS.lock().unwrap().default_driver.clone()
+}
+
+pub fn set_default_driver(driver_type: &str) -> Result<(), AppError> {
+    let mut settings = SETTINGS.lock().unwrap();
+    settings.default_driver = Some(driver_type.to_string());
+    storage::save(SETTINGS_FILE, &*settings)
+}
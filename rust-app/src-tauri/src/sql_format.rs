@@ -0,0 +1,126 @@
+// Palavras-chave passadas para maiúsculas na exibição; identificadores ficam como vieram
+const KEYWORDS: &[&str] = &[
+    "all", "and", "as", "asc", "between", "by", "case", "create", "cross", "delete",
+    "desc", "distinct", "drop", "else", "end", "exists", "from", "full", "group", "having", "in",
+    "inner", "insert", "into", "is", "join", "left", "like", "limit", "not", "null", "offset",
+    "on", "or", "order", "outer", "right", "select", "set", "table", "then", "union", "update",
+    "values", "when", "where", "with",
+];
+
+fn is_keyword(word: &str) -> bool {
+    KEYWORDS.contains(&word.to_lowercase().as_str())
+}
+
+// Depois de "." é coluna ou tabela ("pedidos.desc"), mesmo com nome de palavra-chave
+fn push_word(formatted: &mut String, word: &mut String) {
+    if is_keyword(word) && !formatted.ends_with('.') {
+        formatted.push_str(&word.to_uppercase());
+    } else {
+        formatted.push_str(word);
+    }
+    word.clear();
+}
+
+// Junta espaços repetidos, põe palavras-chave em maiúsculas e termina com um único ';'.
+// Textos entre aspas e comentários não são tocados; o "--" mantém a quebra de linha,
+// senão comentaria o resto da instrução
+pub fn format_sql(sql: &str) -> String {
+    let mut formatted = String::with_capacity(sql.len());
+    let mut word = String::new();
+    let mut chars = sql.trim().chars().peekable();
+    let mut open_line_comment = false;
+
+    while let Some(c) = chars.next() {
+        if c.is_alphanumeric() || c == '_' {
+            word.push(c);
+            continue;
+        }
+        push_word(&mut formatted, &mut word);
+
+        match c {
+            '\'' | '"' | '`' => {
+                formatted.push(c);
+                for quoted in chars.by_ref() {
+                    formatted.push(quoted);
+                    if quoted == c {
+                        break;
+                    }
+                }
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                formatted.push(c);
+                open_line_comment = true;
+                for commented in chars.by_ref() {
+                    formatted.push(commented);
+                    if commented == '\n' {
+                        open_line_comment = false;
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                formatted.push(c);
+                formatted.extend(chars.next());
+                let mut previous = '\0';
+                for commented in chars.by_ref() {
+                    formatted.push(commented);
+                    if previous == '*' && commented == '/' {
+                        break;
+                    }
+                    previous = commented;
+                }
+            }
+            c if c.is_whitespace() => {
+                if !formatted.ends_with(char::is_whitespace) {
+                    formatted.push(' ');
+                }
+            }
+            // ";;" e "; ;" viram um só separador
+            ';' => {
+                let trimmed = formatted.trim_end().len();
+                if !formatted[..trimmed].ends_with(';') {
+                    formatted.truncate(trimmed);
+                    formatted.push(';');
+                }
+            }
+            c => formatted.push(c),
+        }
+    }
+    push_word(&mut formatted, &mut word);
+
+    let statement = formatted.trim_end().trim_end_matches(';').trim_end();
+    if statement.is_empty() {
+        return String::new();
+    }
+    // Comentário de linha no fim engoliria o ';'
+    if open_line_comment {
+        format!("{}\n;", statement)
+    } else {
+        format!("{};", statement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messy_statement_is_normalized() {
+        assert_eq!(
+            format_sql("  select  id,\n\tnome from   pedidos where nome = 'a  b' ;; "),
+            "SELECT id, nome FROM pedidos WHERE nome = 'a  b';"
+        );
+    }
+
+    #[test]
+    fn clean_statement_is_unchanged() {
+        let sql = "SELECT p.desc FROM pedidos p ORDER BY p.id DESC LIMIT 10;";
+        assert_eq!(format_sql(sql), sql);
+    }
+
+    #[test]
+    fn trailing_line_comment_keeps_the_semicolon_outside() {
+        assert_eq!(format_sql("select 1 -- total"), "SELECT 1 -- total\n;");
+        assert_eq!(format_sql(" ;; "), "");
+    }
+}
@@ -0,0 +1,43 @@
+use crate::error::AppError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+// Diretório de dados do app, definido no setup. Sem ele os dados ficam só em memória
+static DATA_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+pub fn set_data_dir(dir: PathBuf) {
+    *DATA_DIR.lock().unwrap() = Some(dir);
+}
+
+pub fn path(file_name: &str) -> Option<PathBuf> {
+    DATA_DIR.lock().unwrap().as_ref().map(|dir| dir.join(file_name))
+}
+
+// Arquivo ausente ou corrompido resulta no valor padrão em vez de impedir o app de abrir
+pub fn load<T: DeserializeOwned + Default>(file_name: &str) -> T {
+    let Some(path) = path(file_name) else {
+        return T::default();
+    };
+
+    match std::fs::read_to_string(&path) {
+        Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+            log::warn!("Ignorando {} inválido: {}", path.display(), e);
+            T::default()
+        }),
+        Err(_) => T::default(),
+    }
+}
+
+pub fn save<T: Serialize>(file_name: &str, value: &T) -> Result<(), AppError> {
+    let Some(path) = path(file_name) else {
+        return Ok(());
+    };
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| AppError::Io(e.to_string()))?;
+    }
+    let text = serde_json::to_string_pretty(value).map_err(|e| AppError::Io(e.to_string()))?;
+    std::fs::write(&path, text).map_err(|e| AppError::Io(e.to_string()))
+}
@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+// Os testes rodam em paralelo, mas a configuração de segurança, a do backend e o idioma
+// são globais; quem mexe nesse estado segura esta trava. É do tokio para poder ficar
+// com os testes assíncronos entre um await e outro
+static SERIAL: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+pub type SerialGuard = tokio::sync::MutexGuard<'static, ()>;
+
+pub fn serial() -> SerialGuard {
+    SERIAL.blocking_lock()
+}
+
+pub async fn serial_async() -> SerialGuard {
+    SERIAL.lock().await
+}
+
+// Diretório novo a cada chamada, para arquivos de um teste não vazarem para outro
+pub fn temp_dir() -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("rust-app-test-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    // Nomes em minúsculas
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase()).map(String::as_str)
+    }
+}
+
+pub struct Response {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    delay: Duration,
+    truncated: bool,
+}
+
+impl Response {
+    pub fn new(status: u16, content_type: &str, body: impl Into<Vec<u8>>) -> Self {
+        Response {
+            status,
+            headers: vec![("Content-Type".to_string(), content_type.to_string())],
+            body: body.into(),
+            delay: Duration::ZERO,
+            truncated: false,
+        }
+    }
+
+    pub fn json(value: serde_json::Value) -> Self {
+        Response::new(200, "application/json", value.to_string())
+    }
+
+    pub fn status(status: u16) -> Self {
+        Response::new(status, "application/json", "{}")
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn delayed(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    // Corpo compactado com gzip, como um backend atrás de um proxy com compressão
+    pub fn gzipped(mut self) -> Self {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&self.body).unwrap();
+        self.body = encoder.finish().unwrap();
+        self.headers.push(("Content-Encoding".to_string(), "gzip".to_string()));
+        self
+    }
+
+    // Anuncia mais bytes do que envia e fecha a conexão, como um backend que caiu no meio
+    pub fn truncated(mut self) -> Self {
+        self.truncated = true;
+        self
+    }
+}
+
+type Handler = dyn Fn(&Request, usize) -> Response + Send + Sync;
+
+// Servidor HTTP mínimo numa porta livre, uma requisição por conexão. O handler recebe
+// a requisição e quantas vieram antes dela, para respostas como "falha duas vezes e depois funciona"
+pub struct MockServer {
+    scheme: &'static str,
+    port: u16,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+    pub fn start(handler: impl Fn(&Request, usize) -> Response + Send + Sync + 'static) -> Self {
+        Self::listen(None, handler)
+    }
+
+    // Mesmo servidor atrás de TLS, com o certificado e a chave (PEM) recebidos
+    pub fn start_tls(
+        certificate: &SelfSigned,
+        handler: impl Fn(&Request, usize) -> Response + Send + Sync + 'static,
+    ) -> Self {
+        let identity = native_tls::Identity::from_pkcs8(&certificate.cert_pem, &certificate.key_pem).unwrap();
+        let acceptor = native_tls::TlsAcceptor::new(identity).unwrap();
+        Self::listen(Some(Arc::new(acceptor)), handler)
+    }
+
+    fn listen(
+        acceptor: Option<Arc<native_tls::TlsAcceptor>>,
+        handler: impl Fn(&Request, usize) -> Response + Send + Sync + 'static,
+    ) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler: Arc<Handler> = Arc::new(handler);
+        let scheme = if acceptor.is_some() { "https" } else { "http" };
+
+        let recorded = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let recorded = recorded.clone();
+                let handler = handler.clone();
+                let acceptor = acceptor.clone();
+                std::thread::spawn(move || match acceptor {
+                    // Handshake recusado: o cliente não confia no certificado
+                    Some(acceptor) => {
+                        if let Ok(stream) = acceptor.accept(stream) {
+                            serve(stream, &recorded, handler.as_ref());
+                        }
+                    }
+                    None => serve(stream, &recorded, handler.as_ref()),
+                });
+            }
+        });
+
+        MockServer { scheme, port, requests }
+    }
+
+    pub fn url(&self) -> String {
+        format!("{}://127.0.0.1:{}", self.scheme, self.port)
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    pub fn hits(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+}
+
+fn serve<S: Read + Write>(stream: S, recorded: &Mutex<Vec<Request>>, handler: &Handler) {
+    let mut reader = BufReader::new(stream);
+    let Some(request) = read_request(&mut reader) else {
+        return;
+    };
+    let index = {
+        let mut recorded = recorded.lock().unwrap();
+        recorded.push(request.clone());
+        recorded.len() - 1
+    };
+
+    let response = handler(&request, index);
+    std::thread::sleep(response.delay);
+
+    let mut head = format!(
+        "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.body.len() + if response.truncated { 64 } else { 0 }
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+
+    // O cliente pode já ter desistido (timeout, cancelamento)
+    let mut stream = reader.into_inner();
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(&response.body);
+    let _ = stream.flush();
+}
+
+fn read_request<S: Read>(reader: &mut BufReader<S>) -> Option<Request> {
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let path = parts.next()?.to_string();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let length = headers.get("content-length").and_then(|value| value.parse().ok()).unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok()?;
+
+    Some(Request {
+        method,
+        path,
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+// Configuração padrão do backend apontando para o mock, com esperas curtas entre tentativas
+pub fn use_backend(url: &str) {
+    crate::api_client::init_backend_config(None);
+    crate::api_client::set_base_url(url).unwrap();
+    crate::api_client::set_retry_policy(3, 1);
+}
+
+// Porta em que nada escuta: o listener é aberto só para o sistema escolher uma livre
+pub fn closed_port_url() -> String {
+    let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    format!("http://127.0.0.1:{}", port)
+}
+
+pub struct SelfSigned {
+    pub cert_pem: Vec<u8>,
+    pub key_pem: Vec<u8>,
+}
+
+// Certificado autoassinado para 127.0.0.1, que faz as vezes de uma CA privada
+pub fn self_signed_cert() -> SelfSigned {
+    use openssl::asn1::Asn1Time;
+    use openssl::hash::MessageDigest;
+    use openssl::pkey::PKey;
+    use openssl::rsa::Rsa;
+    use openssl::x509::extension::{BasicConstraints, SubjectAlternativeName};
+    use openssl::x509::{X509NameBuilder, X509};
+
+    let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+    let mut name = X509NameBuilder::new().unwrap();
+    name.append_entry_by_text("CN", "127.0.0.1").unwrap();
+    let name = name.build();
+
+    let mut builder = X509::builder().unwrap();
+    builder.set_version(2).unwrap();
+    builder.set_subject_name(&name).unwrap();
+    builder.set_issuer_name(&name).unwrap();
+    builder.set_pubkey(&key).unwrap();
+    builder.set_not_before(&Asn1Time::days_from_now(0).unwrap()).unwrap();
+    builder.set_not_after(&Asn1Time::days_from_now(1).unwrap()).unwrap();
+    builder.append_extension(BasicConstraints::new().critical().ca().build().unwrap()).unwrap();
+    let san = SubjectAlternativeName::new().ip("127.0.0.1").build(&builder.x509v3_context(None, None)).unwrap();
+    builder.append_extension(san).unwrap();
+    builder.sign(&key, MessageDigest::sha256()).unwrap();
+
+    SelfSigned {
+        cert_pem: builder.build().to_pem().unwrap(),
+        key_pem: key.private_key_to_pem_pkcs8().unwrap(),
+    }
+}
@@ -1,7 +1,8 @@
-import { useState } from 'react';
+import { useEffect, useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import DatabaseConfig from './components/DatabaseConfig';
 import RefreshButton from './components/RefreshButton';
+import { errorMessage, failedSql } from './errors';
 
 interface DatabaseStatus {
   connected: boolean;
@@ -18,6 +19,15 @@ function App() {
     tables: []
   });
 
+  // Fecha as conexões do backend antes de a janela sumir
+  useEffect(() => {
+    const handleUnload = () => {
+      invoke('prepare_shutdown');
+    };
+    window.addEventListener('beforeunload', handleUnload);
+    return () => window.removeEventListener('beforeunload', handleUnload);
+  }, []);
+
   async function sendQuestion() {
     if (!databaseStatus.connected) {
       setResponse('❌ Conecte-se a um banco de dados primeiro!');
@@ -28,7 +38,8 @@ function App() {
       const res = await invoke<string>('send_query', { question });
       setResponse(res);
     } catch (error) {
-      setResponse('❌ Erro ao conectar com backend.');
+      const sql = failedSql(error);
+      setResponse(sql ? `${errorMessage(error)}\n\n📝 SQL: ${sql}` : errorMessage(error));
     }
   }
 
@@ -1,6 +1,8 @@
 import React, { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import './DatabaseConfig.css';
+import { errorMessage } from '../errors';
 
 interface Driver {
   name: string;
@@ -15,6 +17,14 @@ interface DatabaseStatus {
   tables: string[];
 }
 
+type ConnectStage = 'started' | 'validating' | 'connecting' | 'finished' | 'failed' | 'cancelled';
+
+const STAGE_LABELS: Partial<Record<ConnectStage, string>> = {
+  started: 'Iniciando...',
+  validating: 'Validando configuração...',
+  connecting: 'Conectando...'
+};
+
 interface DatabaseConfigProps {
   onConnectionChange: (status: DatabaseStatus) => void;
 }
@@ -24,6 +34,7 @@ const DatabaseConfig: React.FC<DatabaseConfigProps> = ({ onConnectionChange }) =
   const [selectedDriver, setSelectedDriver] = useState<string>('');
   const [config, setConfig] = useState<Record<string, any>>({});
   const [isConnecting, setIsConnecting] = useState(false);
+  const [connectStage, setConnectStage] = useState<ConnectStage | null>(null);
   const [connectionStatus, setConnectionStatus] = useState<DatabaseStatus>({
     connected: false,
     driver_type: null,
@@ -35,6 +46,13 @@ const DatabaseConfig: React.FC<DatabaseConfigProps> = ({ onConnectionChange }) =
   useEffect(() => {
     loadDrivers();
     checkConnectionStatus();
+
+    const unlisten = listen<ConnectStage>('connect-progress', (event) => {
+      setConnectStage(event.payload);
+    });
+    return () => {
+      unlisten.then((stop) => stop());
+    };
   }, []);
 
   const loadDrivers = async () => {
@@ -114,9 +132,10 @@ const DatabaseConfig: React.FC<DatabaseConfigProps> = ({ onConnectionChange }) =
         setMessage(`❌ ${result.message}`);
       }
     } catch (error) {
-      setMessage(`❌ Erro: ${error}`);
+      setMessage(`❌ Erro: ${errorMessage(error)}`);
     } finally {
       setIsConnecting(false);
+      setConnectStage(null);
     }
   };
 
@@ -132,7 +151,7 @@ const DatabaseConfig: React.FC<DatabaseConfigProps> = ({ onConnectionChange }) =
       setConnectionStatus(newStatus);
       onConnectionChange(newStatus);
     } catch (error) {
-      setMessage(`❌ Erro ao desconectar: ${error}`);
+      setMessage(`❌ Erro ao desconectar: ${errorMessage(error)}`);
     }
   };
 
@@ -147,7 +166,7 @@ const DatabaseConfig: React.FC<DatabaseConfigProps> = ({ onConnectionChange }) =
         setMessage(`❌ Erro ao criar dados: ${result.message}`);
       }
     } catch (error) {
-      setMessage(`❌ Erro: ${error}`);
+      setMessage(`❌ Erro: ${errorMessage(error)}`);
     }
   };
 
@@ -282,7 +301,7 @@ const DatabaseConfig: React.FC<DatabaseConfigProps> = ({ onConnectionChange }) =
                   disabled={isConnecting}
                   className="btn btn-primary"
                 >
-                  {isConnecting ? 'Conectando...' : 'Conectar'}
+                  {isConnecting ? (connectStage && STAGE_LABELS[connectStage]) || 'Conectando...' : 'Conectar'}
                 </button>
               </div>
             </div>
@@ -0,0 +1,25 @@
+export interface AppError {
+  code: string;
+  message: string;
+  status?: number;
+  body?: string;
+  sql?: string;
+  request_id?: string;
+}
+
+export function errorMessage(error: unknown): string {
+  if (typeof error === 'object' && error !== null && 'message' in error) {
+    const { message, request_id } = error as AppError;
+    // O id permite localizar a falha nos logs do backend
+    return request_id ? `${message} (id: ${request_id})` : message;
+  }
+  return String(error);
+}
+
+// SQL gerado antes da falha, quando o backend chegou a produzi-lo
+export function failedSql(error: unknown): string | undefined {
+  if (typeof error === 'object' && error !== null && 'sql' in error) {
+    return (error as AppError).sql;
+  }
+  return undefined;
+}